@@ -37,6 +37,76 @@ pub enum Error {
     MinterNotSet = 15,
     /// Token address not set
     TokenNotSet = 16,
+    /// Validator is already part of the delegation set
+    ValidatorAlreadyExists = 17,
+    /// Validator is not part of the delegation set
+    ValidatorNotFound = 18,
+    /// Validator still holds delegated CSPR
+    ValidatorHasDelegation = 19,
+    /// Validator set is full
+    TooManyValidators = 20,
+    /// No validator in the set has a non-zero weight
+    NoActiveValidators = 21,
+    /// A validator migration is already in progress
+    MigrationInProgress = 22,
+    /// No validator migration is in progress
+    NoMigrationInProgress = 23,
+    /// Undelegated CSPR has not come back from unbonding yet
+    NothingToRedelegate = 24,
+    /// Instant unstake fee is above the caller's limit
+    InstantFeeTooHigh = 25,
+    /// Liquidity buffer settings are out of range
+    InvalidBufferConfig = 26,
+    /// Caller is neither owner nor approved for the withdrawal
+    NotWithdrawalApproved = 27,
+    /// Pending delegations are below the delegation threshold
+    BelowDelegationThreshold = 28,
+    /// Unbonded CSPR has not reached the contract yet
+    FundsNotYetUnbonded = 29,
+    /// Unbonding delay is out of range
+    InvalidUnbondingDelay = 30,
+    /// Minimum interval since the last compound has not elapsed
+    CompoundTooSoon = 31,
+    /// Keeper reward settings are out of range
+    InvalidKeeperConfig = 32,
+    /// Not enough liquid CSPR in the pool to pay out
+    InsufficientLiquidity = 33,
+    /// Fee recipients are invalid (shares must be non-zero and add up to 100%)
+    InvalidFeeRecipients = 34,
+    /// Stake is too small to mint any thCSPR
+    ZeroSharesMinted = 35,
+    /// Referrer cannot be the stake recipient
+    InvalidReferrer = 36,
+    /// Stake would push total pooled CSPR above the global cap
+    StakeCapExceeded = 37,
+    /// Stake would push the recipient's position above the per-address cap
+    AddressCapExceeded = 38,
+    /// Allowlist mode is on and the recipient is not allowlisted
+    NotAllowlisted = 39,
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 40,
+    /// Caller is not the proposed admin
+    NotPendingAdmin = 41,
+    /// No minter transfer has been proposed
+    NoPendingMinter = 42,
+    /// Caller is not the proposed minter
+    NotPendingMinter = 43,
+    /// Caller lacks the role required for this operation
+    MissingRole = 44,
+    /// Role is not one of the protocol roles
+    InvalidRole = 45,
+    /// No queued timelock operation with this id
+    OperationNotQueued = 46,
+    /// Timelock operation ETA has not been reached yet
+    TimelockNotReady = 47,
+    /// Timelock delay above the allowed maximum
+    InvalidTimelockDelay = 48,
+    /// Migration source and destination are the same validator
+    SameValidator = 49,
+    /// Timelocked call belongs to the LendingPool
+    UnsupportedTimelockCall = 50,
+    /// Not enough CSPR backs the withdrawal
+    InsufficientBacking = 51,
 }
 
 /// Lending pool errors
@@ -62,4 +132,48 @@ pub enum LendingError {
     InvalidLoopCount = 108,
     /// Caller is not admin
     NotAdmin = 109,
+    /// Fee recipients are invalid (shares must be non-zero and add up to 100%)
+    InvalidFeeRecipients = 110,
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 111,
+    /// Caller is not the proposed admin
+    NotPendingAdmin = 112,
+    /// Caller lacks the role required for this operation
+    MissingRole = 113,
+    /// Role is not one of the protocol roles
+    InvalidRole = 114,
+    /// No queued timelock operation with this id
+    OperationNotQueued = 115,
+    /// Timelock operation ETA has not been reached yet
+    TimelockNotReady = 116,
+    /// Timelock delay above the allowed maximum
+    InvalidTimelockDelay = 117,
+    /// This lending action is paused
+    ActionPaused = 118,
+    /// Deposit too small to mint any lender shares
+    ZeroSharesMinted = 119,
+    /// Interest rate model parameters are out of range
+    InvalidRateModel = 120,
+    /// tCSPR token address not set yet
+    LenderTokenNotSet = 121,
+    /// tCSPR token address can only be set once
+    LenderTokenAlreadySet = 122,
+    /// Reserve factor above the maximum
+    InvalidReserveFactor = 123,
+    /// Withdrawal exceeds the protocol reserves
+    InsufficientReserves = 124,
+    /// Target leverage below 1x or above what the collateral factor allows
+    InvalidLeverage = 125,
+    /// Fewer thCSPR minted than the caller's minimum
+    SlippageExceeded = 126,
+    /// The position already has an unwind waiting in the withdrawal queue
+    UnwindPending = 127,
+    /// The position has no queued unwind to settle
+    NoUnwindPending = 128,
+    /// Nothing to deleverage or close in this position
+    NothingToUnwind = 129,
+    /// Reserves cannot be withdrawn before fee recipients are configured
+    FeeRecipientsNotSet = 130,
+    /// Timelocked call belongs to ThawCore
+    UnsupportedTimelockCall = 131,
 }
@@ -1,7 +1,10 @@
 //! Events for Thaw protocol (CEP-88 compliant)
 
 use odra::prelude::*;
-use odra::casper_types::U512;
+use odra::casper_types::{PublicKey, U512};
+
+use crate::lending_pool::LendingAction;
+use crate::thaw_core::FeeMode;
 
 /// Emitted when a user stakes CSPR
 #[odra::event]
@@ -10,6 +13,7 @@ pub struct Staked {
     pub cspr_amount: U512,
     pub thcspr_minted: U512,
     pub exchange_rate: U512,
+    pub referrer: Option<Address>,
 }
 
 /// Emitted when a user requests to unstake
@@ -30,6 +34,32 @@ pub struct Claimed {
     pub cspr_amount: U512,
 }
 
+/// Emitted when a withdrawal request changes owner
+#[odra::event]
+pub struct WithdrawalTransferred {
+    pub withdrawal_id: u64,
+    pub from: Address,
+    pub to: Address,
+}
+
+/// Emitted when a withdrawal owner approves (or revokes) a spender
+#[odra::event]
+pub struct WithdrawalApproved {
+    pub withdrawal_id: u64,
+    pub owner: Address,
+    pub spender: Option<Address>,
+}
+
+/// Emitted when a user unstakes instantly from the liquidity buffer
+#[odra::event]
+pub struct InstantUnstaked {
+    pub user: Address,
+    pub thcspr_burned: U512,
+    pub cspr_amount: U512,
+    pub fee: U512,
+    pub fee_bps: u64,
+}
+
 /// Emitted when rewards are compounded
 #[odra::event]
 pub struct Compounded {
@@ -37,6 +67,11 @@ pub struct Compounded {
     pub protocol_fee: U512,
     pub rewards_to_pool: U512,
     pub new_exchange_rate: U512,
+    /// Caller of `compound`, paid the keeper reward
+    pub keeper: Address,
+    pub keeper_reward: U512,
+    /// How the protocol fee was paid to the treasury
+    pub fee_mode: FeeMode,
 }
 
 /// Emitted when contract is paused
@@ -58,13 +93,219 @@ pub struct FeeUpdated {
     pub new_fee_bps: u64,
 }
 
-/// Emitted when admin is transferred
+/// Emitted when the protocol fee mode is updated
+#[odra::event]
+pub struct FeeModeUpdated {
+    pub old_mode: FeeMode,
+    pub new_mode: FeeMode,
+}
+
+/// Emitted when the protocol revenue recipients are updated
+#[odra::event]
+pub struct FeeRecipientsUpdated {
+    pub accounts: Vec<Address>,
+    pub shares_bps: Vec<u64>,
+}
+
+/// Emitted when the liquidity buffer settings are updated
+#[odra::event]
+pub struct LiquidityBufferConfigUpdated {
+    pub target_bps: u64,
+    pub stake_share_bps: u64,
+    pub min_fee_bps: u64,
+    pub max_fee_bps: u64,
+}
+
+/// Emitted when the compound keeper reward settings are updated
+#[odra::event]
+pub struct KeeperRewardConfigUpdated {
+    pub reward_bps: u64,
+    pub max_reward: U512,
+    pub min_interval_ms: u64,
+}
+
+/// Emitted when the unbonding delay for new withdrawals is updated
+#[odra::event]
+pub struct UnbondingDelayUpdated {
+    pub old_delay_ms: u64,
+    pub new_delay_ms: u64,
+}
+
+/// Emitted when the stake caps are updated (zero means no cap)
+#[odra::event]
+pub struct StakeCapsUpdated {
+    pub global_cap: U512,
+    pub per_address_cap: U512,
+}
+
+/// Emitted when allowlist mode is switched on or off
+#[odra::event]
+pub struct AllowlistModeUpdated {
+    pub enabled: bool,
+}
+
+/// Emitted when an address is added to or removed from the allowlist
+#[odra::event]
+pub struct AllowlistUpdated {
+    pub account: Address,
+    pub allowed: bool,
+}
+
+/// Emitted when an address is exempted from the per-address cap or loses the exemption
+#[odra::event]
+pub struct CapExemptionUpdated {
+    pub account: Address,
+    pub exempt: bool,
+}
+
+/// Emitted when the admin proposes a new admin
+#[odra::event]
+pub struct AdminTransferProposed {
+    pub current_admin: Address,
+    pub pending_admin: Address,
+}
+
+/// Emitted when a proposed admin transfer is cancelled
+#[odra::event]
+pub struct AdminTransferCancelled {
+    pub cancelled_admin: Address,
+}
+
+/// Emitted when the proposed admin accepts and admin is transferred
 #[odra::event]
 pub struct AdminTransferred {
     pub old_admin: Address,
     pub new_admin: Address,
 }
 
+/// Emitted when the thCSPR minter proposes a new minter
+#[odra::event]
+pub struct MinterTransferProposed {
+    pub current_minter: Address,
+    pub pending_minter: Address,
+}
+
+/// Emitted when a proposed minter transfer is cancelled
+#[odra::event]
+pub struct MinterTransferCancelled {
+    pub cancelled_minter: Address,
+}
+
+/// Emitted when the proposed minter accepts and minting rights move
+#[odra::event]
+pub struct MinterTransferred {
+    pub old_minter: Address,
+    pub new_minter: Address,
+}
+
+/// Emitted when a validator is added to the delegation set
+#[odra::event]
+pub struct ValidatorAdded {
+    pub validator: PublicKey,
+    pub weight: u64,
+}
+
+/// Emitted when a validator is removed from the delegation set
+#[odra::event]
+pub struct ValidatorRemoved {
+    pub validator: PublicKey,
+}
+
+/// Emitted when a validator's target weight is updated
+#[odra::event]
+pub struct ValidatorWeightUpdated {
+    pub validator: PublicKey,
+    pub old_weight: u64,
+    pub new_weight: u64,
+}
+
+/// Emitted when delegation starts moving from one validator to another
+#[odra::event]
+pub struct ValidatorMigrationStarted {
+    pub from: PublicKey,
+    pub to: PublicKey,
+    pub amount: U512,
+}
+
+/// Emitted when unbonded CSPR from a migration is re-delegated
+#[odra::event]
+pub struct ValidatorRedelegated {
+    pub from: PublicKey,
+    pub to: PublicKey,
+    pub amount: U512,
+    pub remaining: U512,
+}
+
+/// Emitted when a validator migration has been fully re-delegated
+#[odra::event]
+pub struct ValidatorMigrationCompleted {
+    pub from: PublicKey,
+    pub to: PublicKey,
+    pub amount: U512,
+}
+
+/// Emitted when batched stakes are delegated to the validator set
+#[odra::event]
+pub struct DelegationsFlushed {
+    pub amount: U512,
+}
+
+/// Emitted when the delegation batching threshold is updated
+#[odra::event]
+pub struct DelegationThresholdUpdated {
+    pub old_threshold: U512,
+    pub new_threshold: U512,
+}
+
+/// Emitted when a validator's delegation was unbonded by the auction
+///
+/// The CSPR is tracked as pool CSPR unbonding back to the contract.
+#[odra::event]
+pub struct ValidatorEvicted {
+    pub validator: PublicKey,
+    pub amount: U512,
+}
+
+/// Emitted when a shortfall in the pool (slashing, unrecovered unbonding) is socialized
+#[odra::event]
+pub struct Slashed {
+    pub loss: U512,
+    /// Part of the loss taken from total pooled CSPR (thCSPR holders)
+    pub holders_loss: U512,
+    /// Part of the loss taken from pending withdrawal requests
+    pub withdrawals_loss: U512,
+    pub old_exchange_rate: U512,
+    pub new_exchange_rate: U512,
+}
+
+/// Emitted when a parameter change is queued behind the timelock
+///
+/// The queued call is read with `get_queued_operation(id)`.
+#[odra::event]
+pub struct OperationQueued {
+    pub id: u64,
+    pub eta: u64,
+}
+
+/// Emitted when a queued parameter change is executed
+#[odra::event]
+pub struct OperationExecuted {
+    pub id: u64,
+}
+
+/// Emitted when a queued parameter change is cancelled
+#[odra::event]
+pub struct OperationCancelled {
+    pub id: u64,
+}
+
+/// Emitted when the timelock delay is updated
+#[odra::event]
+pub struct TimelockDelayUpdated {
+    pub old_delay_ms: u64,
+    pub new_delay_ms: u64,
+}
+
 // ============ LENDING POOL EVENTS ============
 
 /// Emitted when a lender deposits CSPR to the lending pool
@@ -125,12 +366,86 @@ pub struct Liquidated {
     pub collateral_seized: U512,
 }
 
+/// Emitted when the share of interest kept as reserves is updated
+#[odra::event]
+pub struct ReserveFactorUpdated {
+    pub old_reserve_factor_bps: u64,
+    pub new_reserve_factor_bps: u64,
+}
+
+/// Emitted for each recipient paid when protocol reserves are withdrawn
+#[odra::event]
+pub struct ReservesWithdrawn {
+    pub to: Address,
+    pub amount: U512,
+    pub remaining_reserves: U512,
+}
+
+/// Emitted when a liquidation leaves debt without collateral
+///
+/// Reserves absorb the loss first; the rest is taken from lender deposits.
+#[odra::event]
+pub struct BadDebtCovered {
+    pub borrower: Address,
+    pub bad_debt: U512,
+    pub covered_by_reserves: U512,
+    pub lender_loss: U512,
+}
+
+/// Emitted when the staking APR used for leverage previews is updated
+#[odra::event]
+pub struct StakingAprUpdated {
+    pub old_apr_bps: u64,
+    pub new_apr_bps: u64,
+}
+
+/// Emitted when a lending action is paused or unpaused
+#[odra::event]
+pub struct LendingActionPauseUpdated {
+    pub action: LendingAction,
+    pub paused: bool,
+    pub by: Address,
+}
+
+/// Emitted when the interest rate model is updated
+#[odra::event]
+pub struct InterestRateModelUpdated {
+    pub base_rate_bps: u64,
+    pub slope1_bps: u64,
+    pub optimal_utilization_bps: u64,
+    pub slope2_bps: u64,
+}
+
 /// Emitted when a user performs leveraged staking
 #[odra::event]
 pub struct LeveragedStake {
     pub user: Address,
     pub initial_amount: U512,
     pub total_staked: U512,
+    /// thCSPR minted, all of it added to the user's collateral
     pub total_thcspr: U512,
+    pub total_borrowed: U512,
+    /// Loops run, fewer than requested if liquidity ran out (1 for a target leverage)
     pub leverage_loops: u8,
 }
+
+/// Emitted when sold collateral pays down a position
+#[odra::event]
+pub struct PositionUnwound {
+    pub user: Address,
+    /// thCSPR unstaked out of the user's collateral
+    pub thcspr_sold: U512,
+    /// CSPR received from ThawCore
+    pub cspr_received: U512,
+    pub debt_repaid: U512,
+    /// CSPR left after repaying, sent to the user
+    pub returned: U512,
+}
+
+/// Emitted when an unwind goes through the ThawCore withdrawal queue
+#[odra::event]
+pub struct UnwindQueued {
+    pub user: Address,
+    pub withdrawal_id: u64,
+    pub thcspr_sold: U512,
+}
@@ -0,0 +1,96 @@
+//! FeeSplitter - Splits protocol revenue between several recipients
+//!
+//! Used as a submodule by ThawCore (reward fees) and LendingPool (interest
+//! reserve). The host contract checks admin rights, validates the recipients
+//! and moves the funds; the splitter works out each recipient's part and keeps
+//! per-recipient accrued totals.
+
+use alloc::vec::Vec;
+use odra::prelude::*;
+use odra::casper_types::U512;
+
+/// Maximum number of fee recipients
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+
+const BPS_PRECISION: u64 = 10_000;
+
+/// A fee recipient and its share of revenue
+#[odra::odra_type]
+pub struct FeeRecipient {
+    pub account: Address,
+    /// Share of every distribution (basis points)
+    pub share_bps: u64,
+}
+
+/// Revenue split between treasury, insurance fund, node operators, etc.
+#[odra::module]
+pub struct FeeSplitter {
+    recipients: Var<Vec<FeeRecipient>>,
+    accrued: Mapping<Address, U512>,
+}
+
+#[odra::module]
+impl FeeSplitter {
+    /// Replace the recipients (check `is_valid_split` first)
+    pub fn set_recipients(&mut self, recipients: Vec<FeeRecipient>) {
+        self.recipients.set(recipients);
+    }
+
+    /// Get the configured recipients
+    pub fn get_recipients(&self) -> Vec<FeeRecipient> {
+        self.recipients.get_or_default()
+    }
+
+    /// Get the total revenue distributed to `account` so far
+    pub fn get_accrued(&self, account: Address) -> U512 {
+        self.accrued.get(&account).unwrap_or_default()
+    }
+}
+
+impl FeeSplitter {
+    /// Split `amount` between the recipients by share
+    ///
+    /// Rounding dust goes to the first recipient. Empty if no recipients
+    /// are configured.
+    pub fn split(&self, amount: U512) -> Vec<(Address, U512)> {
+        let recipients = self.recipients.get_or_default();
+        let mut parts: Vec<(Address, U512)> = recipients
+            .iter()
+            .map(|r| {
+                let part = amount * U512::from(r.share_bps) / U512::from(BPS_PRECISION);
+                (r.account, part)
+            })
+            .collect();
+
+        let allocated = parts.iter().fold(U512::zero(), |acc, (_, part)| acc + *part);
+        if let Some((_, first)) = parts.first_mut() {
+            *first += amount - allocated;
+        }
+        parts
+    }
+
+    /// Add `amount` to the accrued total of `account`
+    pub fn accrue(&mut self, account: Address, amount: U512) {
+        let accrued = self.accrued.get(&account).unwrap_or_default();
+        self.accrued.set(&account, accrued + amount);
+    }
+}
+
+/// Whether `recipients` is a valid split
+///
+/// Either empty, or at most `MAX_FEE_RECIPIENTS` distinct accounts with
+/// non-zero shares adding up to 100%.
+pub fn is_valid_split(recipients: &[FeeRecipient]) -> bool {
+    if recipients.is_empty() {
+        return true;
+    }
+    if recipients.len() > MAX_FEE_RECIPIENTS {
+        return false;
+    }
+    let has_duplicates = recipients
+        .iter()
+        .enumerate()
+        .any(|(i, r)| recipients[..i].iter().any(|other| other.account == r.account));
+    let total: u64 = recipients.iter().map(|r| r.share_bps).sum();
+    !has_duplicates && recipients.iter().all(|r| r.share_bps > 0) && total == BPS_PRECISION
+}
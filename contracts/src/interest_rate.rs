@@ -0,0 +1,75 @@
+//! Interest rate model - Borrow rate as a function of pool utilization
+//!
+//! A kinked model: the borrow rate rises by `slope1` between zero and the
+//! optimal utilization, then by the much steeper `slope2` up to 100%. The
+//! jump past the kink makes borrowing expensive before the pool runs dry,
+//! which pulls in lenders and pushes out borrowers.
+
+use odra::casper_types::U512;
+
+const BPS_PRECISION: u64 = 10_000;
+
+/// Highest borrow rate a model may reach at 100% utilization (1000% APR)
+pub const MAX_BORROW_RATE_BPS: u64 = 100_000;
+
+/// Parameters of the kinked rate model (all in basis points)
+#[odra::odra_type]
+#[derive(Copy)]
+pub struct InterestRateModel {
+    /// Borrow rate at zero utilization
+    pub base_rate_bps: u64,
+    /// Rate added from zero to optimal utilization
+    pub slope1_bps: u64,
+    /// Utilization at which the steep slope starts
+    pub optimal_utilization_bps: u64,
+    /// Rate added from optimal to full utilization
+    pub slope2_bps: u64,
+}
+
+impl InterestRateModel {
+    /// Default model: 2% base, +4% up to 80% utilization, +75% above it
+    pub const DEFAULT: InterestRateModel = InterestRateModel {
+        base_rate_bps: 200,
+        slope1_bps: 400,
+        optimal_utilization_bps: 8000,
+        slope2_bps: 7500,
+    };
+
+    /// Whether the parameters form a usable model
+    ///
+    /// The kink must sit strictly between 0% and 100%, and the rate at full
+    /// utilization must not exceed `MAX_BORROW_RATE_BPS`.
+    pub fn is_valid(&self) -> bool {
+        let max_rate = self
+            .base_rate_bps
+            .checked_add(self.slope1_bps)
+            .and_then(|rate| rate.checked_add(self.slope2_bps));
+        self.optimal_utilization_bps > 0
+            && self.optimal_utilization_bps < BPS_PRECISION
+            && max_rate.is_some_and(|rate| rate <= MAX_BORROW_RATE_BPS)
+    }
+
+    /// Borrow APR (basis points) at `utilization_bps`, capped at 100%
+    pub fn borrow_rate(&self, utilization_bps: u64) -> u64 {
+        let utilization = utilization_bps.min(BPS_PRECISION);
+        let optimal = self.optimal_utilization_bps;
+
+        if utilization <= optimal {
+            self.base_rate_bps + self.slope1_bps * utilization / optimal
+        } else {
+            let excess = utilization - optimal;
+            self.base_rate_bps
+                + self.slope1_bps
+                + self.slope2_bps * excess / (BPS_PRECISION - optimal)
+        }
+    }
+}
+
+/// Utilization of a pool (basis points): `borrowed / deposits`
+pub fn utilization_bps(borrowed: U512, deposits: U512) -> u64 {
+    if deposits == U512::zero() {
+        return 0;
+    }
+    let utilization = borrowed * U512::from(BPS_PRECISION) / deposits;
+    utilization.min(U512::from(BPS_PRECISION)).as_u64()
+}
@@ -4,6 +4,13 @@
 //! - Lenders to deposit CSPR and earn interest from borrowers
 //! - Stakers to use thCSPR as collateral to borrow CSPR
 //! - Leveraged staking by recursively staking borrowed CSPR
+//! - Unwinding leveraged positions by unstaking collateral to repay debt
+//!
+//! Debt grows with a borrow index that accrues interest every time the pool
+//! is touched. Borrower debt is stored scaled by the index, lenders hold
+//! shares of `total_deposits` as tCSPR, whose value grows by the interest
+//! paid net of the reserve factor, which the protocol keeps. The borrow
+//! rate follows a kinked utilization model (see `interest_rate`).
 
 use odra::prelude::*;
 use odra::casper_types::U512;
@@ -12,10 +19,74 @@ use odra::ContractRef;
 use crate::errors::LendingError;
 use crate::events::{
     Deposited, Withdrawn, CollateralDeposited, CollateralWithdrawn,
-    Borrowed, Repaid, Liquidated, LeveragedStake
+    Borrowed, Repaid, Liquidated, LeveragedStake, FeeRecipientsUpdated,
+    AdminTransferProposed, AdminTransferCancelled, AdminTransferred,
+    OperationQueued, OperationExecuted, OperationCancelled, TimelockDelayUpdated,
+    LendingActionPauseUpdated, InterestRateModelUpdated, ReserveFactorUpdated,
+    ReservesWithdrawn, BadDebtCovered, StakingAprUpdated, PositionUnwound, UnwindQueued,
 };
+use crate::fee_splitter::{is_valid_split, FeeRecipient, FeeSplitter};
+use crate::interest_rate::{self, InterestRateModel};
+use crate::share_math::{self, Rounding};
+use crate::roles::{is_known_role, Role, Roles, FEE_MANAGER_ROLE, PAUSER_ROLE, RISK_MANAGER_ROLE};
+use crate::tcspr_token::TCsprTokenContractRef;
 use crate::thcspr_token::ThCsprTokenContractRef;
 use crate::thaw_core::ThawCoreContractRef;
+use crate::timelock::{
+    QueuedOperation, Timelock, TimelockedCall, DEFAULT_TIMELOCK_DELAY_MS, MAX_TIMELOCK_DELAY_MS,
+};
+
+/// Lending actions that can be paused individually
+///
+/// Repaying and adding collateral only make positions safer and can never
+/// be paused.
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum LendingAction {
+    /// Lender deposits
+    Deposit,
+    /// Lender withdrawals and collateral withdrawals
+    Withdraw,
+    Borrow,
+    Leverage,
+    Liquidate,
+}
+
+/// Pause flag of every lending action
+#[odra::odra_type]
+#[derive(Copy)]
+pub struct LendingPauseState {
+    pub deposit: bool,
+    pub withdraw: bool,
+    pub borrow: bool,
+    pub leverage: bool,
+    pub liquidate: bool,
+}
+
+/// Expected outcome of a leveraged stake, see `preview_leverage`
+#[odra::odra_type]
+pub struct LeveragePreview {
+    /// thCSPR minted and posted as collateral
+    pub collateral: U512,
+    /// CSPR borrowed
+    pub debt: U512,
+    /// Health factor of the new position (1e18 = liquidation point)
+    pub health_factor: U512,
+    /// Borrow APR after the borrow moves utilization (basis points)
+    pub borrow_apr_bps: u64,
+    /// Staking yield on the whole position less borrow interest, relative
+    /// to the CSPR put in (basis points, negative when borrowing costs more)
+    pub net_apr_bps: i64,
+}
+
+/// Collateral of a position waiting in the ThawCore withdrawal queue
+#[odra::odra_type]
+pub struct PendingUnwind {
+    /// ThawCore withdrawal request, owned by the pool
+    pub withdrawal_id: u64,
+    /// thCSPR taken out of the position's collateral
+    pub thcspr_amount: U512,
+}
 
 /// LendingPool for leveraged staking
 #[odra::module]
@@ -23,32 +94,65 @@ pub struct LendingPool {
     // Core references
     thaw_core: Var<Address>,
     thcspr_token: Var<Address>,
+    /// tCSPR receipt token minted to lenders (set once after deploy)
+    lender_token: Var<Address>,
 
-    // Pool state
+    // Pool state (both include interest accrued up to `last_accrual_timestamp`)
     total_deposits: Var<U512>,
     total_borrowed: Var<U512>,
 
+    // Interest accrual
+    borrow_index: Var<U512>,
+    last_accrual_timestamp: Var<u64>,
+
+    // Protocol share of interest, held in the pool until withdrawn
+    reserve_factor_bps: Var<u64>,
+    total_reserves: Var<U512>,
+
+    // tCSPR supply, and each lender's average entry rate for interest reporting
+    total_lender_shares: Var<U512>,
+    lender_entry_rates: Mapping<Address, U512>,
+
     // User balances (combined in mappings)
-    lender_deposits: Mapping<Address, U512>,
     collateral_balances: Mapping<Address, U512>,
-    borrowed_balances: Mapping<Address, U512>,
+    /// Debt divided by the borrow index at the time it was taken
+    scaled_debt: Mapping<Address, U512>,
+    /// Unwinds waiting to unbond, still counted as collateral
+    pending_unwinds: Mapping<Address, Option<PendingUnwind>>,
 
     // Configuration (75% collateral factor, 80% liquidation, 5% bonus packed)
-    config: Var<U512>,  // Packed: collateral_factor | liq_threshold | liq_bonus | base_rate
+    config: Var<U512>,  // Packed: collateral_factor | liq_threshold | liq_bonus
+    rate_model: Var<InterestRateModel>,
+    /// Expected thCSPR staking APR, only used for leverage previews
+    staking_apr_bps: Var<u64>,
+
+    // Interest reserve recipients
+    fee_splitter: SubModule<FeeSplitter>,
+
+    // Per-action pause flags
+    pause_state: Var<LendingPauseState>,
 
     // Admin
     admin: Var<Address>,
+    pending_admin: Var<Option<Address>>,
+    roles: SubModule<Roles>,
+    timelock: SubModule<Timelock>,
 }
 
 // Constants
 const PRECISION: u128 = 1_000_000_000_000_000_000; // 1e18
 const BPS_PRECISION: u64 = 10_000; // 100% = 10000 bps
+const MS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
 
 // Default parameters (packed into config)
 const DEFAULT_COLLATERAL_FACTOR: u64 = 7500; // 75%
 const DEFAULT_LIQUIDATION_THRESHOLD: u64 = 8000; // 80%
 const DEFAULT_LIQUIDATION_BONUS: u64 = 500; // 5%
-const DEFAULT_BASE_RATE: u64 = 500; // 5% base APR
+const DEFAULT_RESERVE_FACTOR: u64 = 1000; // 10% of interest
+const DEFAULT_STAKING_APR: u64 = 850; // 8.5% staking APR
+
+/// Largest share of interest the protocol can keep (50%)
+pub const MAX_RESERVE_FACTOR_BPS: u64 = 5000;
 
 #[odra::module]
 impl LendingPool {
@@ -64,13 +168,18 @@ impl LendingPool {
         self.admin.set(admin);
         self.total_deposits.set(U512::zero());
         self.total_borrowed.set(U512::zero());
+        self.borrow_index.set(U512::from(PRECISION));
+        self.last_accrual_timestamp.set(self.env().get_block_time());
 
-        // Pack config: collateral_factor (16 bits) | liq_threshold (16 bits) | liq_bonus (16 bits) | base_rate (16 bits)
+        // Pack config: collateral_factor (16 bits) | liq_threshold (16 bits) | liq_bonus (16 bits)
         let config = U512::from(DEFAULT_COLLATERAL_FACTOR)
             | (U512::from(DEFAULT_LIQUIDATION_THRESHOLD) << 16)
-            | (U512::from(DEFAULT_LIQUIDATION_BONUS) << 32)
-            | (U512::from(DEFAULT_BASE_RATE) << 48);
+            | (U512::from(DEFAULT_LIQUIDATION_BONUS) << 32);
         self.config.set(config);
+        self.rate_model.set(InterestRateModel::DEFAULT);
+        self.reserve_factor_bps.set(DEFAULT_RESERVE_FACTOR);
+        self.staking_apr_bps.set(DEFAULT_STAKING_APR);
+        self.timelock.set_delay(DEFAULT_TIMELOCK_DELAY_MS);
     }
 
     // ============ LENDER FUNCTIONS ============
@@ -78,6 +187,7 @@ impl LendingPool {
     /// Deposit CSPR to the lending pool to earn interest
     #[odra(payable)]
     pub fn deposit(&mut self) {
+        self.require_not_paused(LendingAction::Deposit);
         let caller = self.env().caller();
         let amount = self.env().attached_value();
 
@@ -85,10 +195,34 @@ impl LendingPool {
             self.env().revert(LendingError::AmountMustBePositive);
         }
 
-        let current = self.lender_deposits.get(&caller).unwrap_or_default();
-        self.lender_deposits.set(&caller, current + amount);
+        self.accrue_interest();
+        let total_deposits = self.total_deposits.get_or_default();
+        let total_shares = self.total_lender_shares.get_or_default();
+        let shares = share_math::to_shares(amount, total_deposits, total_shares, Rounding::Down);
+        if shares == U512::zero() {
+            self.env().revert(LendingError::ZeroSharesMinted);
+        }
 
-        let new_total = self.total_deposits.get_or_default() + amount;
+        // Blend this deposit into the lender's average entry rate
+        let current = self.lender_shares_of(caller);
+        let cost = share_math::mul_div(
+            current,
+            self.entry_rate_of(caller),
+            U512::from(PRECISION),
+            Rounding::Up,
+        ) + amount;
+        let entry_rate = share_math::mul_div(
+            cost,
+            U512::from(PRECISION),
+            current + shares,
+            Rounding::Up,
+        );
+        self.lender_entry_rates.set(&caller, entry_rate);
+
+        self.total_lender_shares.set(total_shares + shares);
+        self.lender_token_ref().mint(caller, shares);
+
+        let new_total = total_deposits + amount;
         self.total_deposits.set(new_total);
 
         self.env().emit_event(Deposited {
@@ -98,11 +232,17 @@ impl LendingPool {
         });
     }
 
-    /// Withdraw CSPR from lending pool
+    /// Withdraw CSPR (principal and earned interest) from lending pool
     pub fn withdraw(&mut self, amount: U512) {
+        self.require_not_paused(LendingAction::Withdraw);
         let caller = self.env().caller();
-        let deposit = self.lender_deposits.get(&caller).unwrap_or_default();
 
+        if amount == U512::zero() {
+            self.env().revert(LendingError::AmountMustBePositive);
+        }
+
+        self.accrue_interest();
+        let deposit = self.get_lender_deposit(caller);
         if amount > deposit {
             self.env().revert(LendingError::InsufficientDeposit);
         }
@@ -112,16 +252,31 @@ impl LendingPool {
             self.env().revert(LendingError::InsufficientLiquidity);
         }
 
-        self.lender_deposits.set(&caller, deposit - amount);
-        let new_total = self.total_deposits.get_or_default() - amount;
-        self.total_deposits.set(new_total);
+        // Burn shares worth `amount` (rounded against the lender)
+        let total_deposits = self.total_deposits.get_or_default();
+        let total_shares = self.total_lender_shares.get_or_default();
+        let shares = self.lender_shares_of(caller);
+        let burned = share_math::to_shares(amount, total_deposits, total_shares, Rounding::Up)
+            .min(shares);
+        self.total_lender_shares.set(total_shares - burned);
+        self.total_deposits.set(total_deposits - amount);
+        self.lender_token_ref().burn(caller, burned);
+
+        // Whatever the burned tCSPR cost on entry was principal
+        let principal_part = share_math::mul_div(
+            burned,
+            self.entry_rate_of(caller),
+            U512::from(PRECISION),
+            Rounding::Up,
+        )
+        .min(amount);
 
         self.env().transfer_tokens(&caller, &amount);
 
         self.env().emit_event(Withdrawn {
             lender: caller,
             amount,
-            interest_earned: U512::zero(), // Simplified for hackathon
+            interest_earned: amount - principal_part,
         });
     }
 
@@ -152,6 +307,7 @@ impl LendingPool {
 
     /// Withdraw thCSPR collateral (if health allows)
     pub fn withdraw_collateral(&mut self, amount: U512) {
+        self.require_not_paused(LendingAction::Withdraw);
         let caller = self.env().caller();
         let collateral = self.collateral_balances.get(&caller).unwrap_or_default();
 
@@ -160,11 +316,12 @@ impl LendingPool {
         }
 
         // Check health after withdrawal
+        self.accrue_interest();
         let new_collateral = collateral - amount;
-        let borrowed = self.borrowed_balances.get(&caller).unwrap_or_default();
+        let borrowed = self.debt_of(caller);
 
         if borrowed > U512::zero() {
-            let collateral_value = self.get_collateral_value(new_collateral);
+            let collateral_value = self.position_value(caller, new_collateral);
             let max_borrow = self.calculate_max_borrow(collateral_value);
             if borrowed > max_borrow {
                 self.env().revert(LendingError::WouldBecomeUndercollateralized);
@@ -186,12 +343,14 @@ impl LendingPool {
 
     /// Borrow CSPR against thCSPR collateral
     pub fn borrow(&mut self, amount: U512) {
+        self.require_not_paused(LendingAction::Borrow);
         let caller = self.env().caller();
 
         if amount == U512::zero() {
             self.env().revert(LendingError::AmountMustBePositive);
         }
 
+        self.accrue_interest();
         let available = self.get_available_liquidity();
         if amount > available {
             self.env().revert(LendingError::InsufficientLiquidity);
@@ -199,19 +358,17 @@ impl LendingPool {
 
         // Check collateral
         let collateral = self.collateral_balances.get(&caller).unwrap_or_default();
-        let collateral_value = self.get_collateral_value(collateral);
+        let collateral_value = self.position_value(caller, collateral);
         let max_borrow = self.calculate_max_borrow(collateral_value);
 
-        let current_borrowed = self.borrowed_balances.get(&caller).unwrap_or_default();
+        let current_borrowed = self.debt_of(caller);
         let total_debt = current_borrowed + amount;
 
         if total_debt > max_borrow {
             self.env().revert(LendingError::ExceedsMaxBorrow);
         }
 
-        self.borrowed_balances.set(&caller, total_debt);
-        let new_total_borrowed = self.total_borrowed.get_or_default() + amount;
-        self.total_borrowed.set(new_total_borrowed);
+        self.add_debt(caller, amount);
 
         self.env().transfer_tokens(&caller, &amount);
 
@@ -229,7 +386,8 @@ impl LendingPool {
         let caller = self.env().caller();
         let amount = self.env().attached_value();
 
-        let borrowed = self.borrowed_balances.get(&caller).unwrap_or_default();
+        self.accrue_interest();
+        let borrowed = self.debt_of(caller);
 
         let repay_amount = if amount > borrowed {
             // Refund excess
@@ -240,9 +398,7 @@ impl LendingPool {
             amount
         };
 
-        self.borrowed_balances.set(&caller, borrowed - repay_amount);
-        let new_total_borrowed = self.total_borrowed.get_or_default() - repay_amount;
-        self.total_borrowed.set(new_total_borrowed);
+        self.reduce_debt(caller, repay_amount);
 
         self.env().emit_event(Repaid {
             borrower: caller,
@@ -258,8 +414,14 @@ impl LendingPool {
     /// 1. Stakes initial CSPR to get thCSPR
     /// 2. Uses thCSPR as collateral to borrow more CSPR
     /// 3. Repeats for amplified exposure
+    ///
+    /// Every thCSPR minted, including the last loop's, becomes the caller's
+    /// collateral. Loops stop early once the pool cannot lend at least the
+    /// ThawCore minimum stake, and the whole position must stay healthy.
+    /// `leverage_stake_to` reaches a chosen leverage in a single stake.
     #[odra(payable)]
     pub fn leverage_stake(&mut self, loops: u8) -> U512 {
+        self.require_not_paused(LendingAction::Leverage);
         let caller = self.env().caller();
         let initial_amount = self.env().attached_value();
 
@@ -271,58 +433,210 @@ impl LendingPool {
             self.env().revert(LendingError::InvalidLoopCount);
         }
 
+        self.accrue_interest();
         let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
         let collateral_factor = self.get_collateral_factor();
+        let min_stake = ThawCoreContractRef::new(self.env(), thaw_core).get_min_stake();
 
         let mut total_staked = U512::zero();
         let mut total_thcspr = U512::zero();
+        let mut total_borrowed = U512::zero();
+        let mut loops_run: u8 = 0;
         let mut amount_to_stake = initial_amount;
 
-        for i in 0..loops {
-            // Stake CSPR to get thCSPR
+        loop {
+            // Stake CSPR; the pool holds the thCSPR as the caller's collateral
             let thcspr_received = ThawCoreContractRef::new(self.env(), thaw_core)
                 .with_tokens(amount_to_stake)
                 .stake();
+            let current_collateral = self.collateral_balances.get(&caller).unwrap_or_default();
+            self.collateral_balances.set(&caller, current_collateral + thcspr_received);
+
+            total_staked += amount_to_stake;
+            total_thcspr += thcspr_received;
+            loops_run += 1;
+            if loops_run == loops {
+                break;
+            }
 
-            total_staked = total_staked + amount_to_stake;
-            total_thcspr = total_thcspr + thcspr_received;
+            // Borrow against the new collateral, as far as liquidity allows
+            let collateral_value = self.get_collateral_value(thcspr_received);
+            let borrow_amount = (collateral_value * U512::from(collateral_factor)
+                / U512::from(BPS_PRECISION))
+            .min(self.get_available_liquidity());
+            if borrow_amount.is_zero() || borrow_amount < min_stake {
+                break;
+            }
 
-            // If not last loop, borrow more
-            if i < loops - 1 {
-                // Add thCSPR as collateral
-                let current_collateral = self.collateral_balances.get(&caller).unwrap_or_default();
-                self.collateral_balances.set(&caller, current_collateral + thcspr_received);
+            self.add_debt(caller, borrow_amount);
+            total_borrowed += borrow_amount;
+            amount_to_stake = borrow_amount;
+        }
 
-                // Calculate borrow amount
-                let collateral_value = self.get_collateral_value(thcspr_received);
-                let borrow_amount = collateral_value * U512::from(collateral_factor) / U512::from(BPS_PRECISION);
+        // Earlier debt counts too, so check the whole position
+        if self.get_health_factor(caller) < U512::from(PRECISION) {
+            self.env().revert(LendingError::WouldBecomeUndercollateralized);
+        }
 
-                // Check liquidity
-                let available = self.get_available_liquidity();
-                if borrow_amount > available {
-                    break; // Exit early if not enough liquidity
-                }
+        self.env().emit_event(LeveragedStake {
+            user: caller,
+            initial_amount,
+            total_staked,
+            total_thcspr,
+            total_borrowed,
+            leverage_loops: loops_run,
+        });
 
-                // Record borrow
-                let current_borrowed = self.borrowed_balances.get(&caller).unwrap_or_default();
-                self.borrowed_balances.set(&caller, current_borrowed + borrow_amount);
+        total_thcspr
+    }
 
-                let new_total_borrowed = self.total_borrowed.get_or_default() + borrow_amount;
-                self.total_borrowed.set(new_total_borrowed);
+    /// Stake and borrow in one step to reach `target_leverage_bps`
+    ///
+    /// 25000 turns the attached CSPR into a position worth 2.5x as much,
+    /// with 1.5x borrowed. All minted thCSPR becomes the caller's collateral.
+    /// Reverts if fewer than `min_thcspr_out` thCSPR are minted.
+    #[odra(payable)]
+    pub fn leverage_stake_to(&mut self, target_leverage_bps: u64, min_thcspr_out: U512) -> U512 {
+        self.require_not_paused(LendingAction::Leverage);
+        let caller = self.env().caller();
+        let initial_amount = self.env().attached_value();
 
-                amount_to_stake = borrow_amount;
-            }
+        if initial_amount == U512::zero() {
+            self.env().revert(LendingError::AmountMustBePositive);
+        }
+        if target_leverage_bps < BPS_PRECISION || target_leverage_bps > self.get_max_leverage() {
+            self.env().revert(LendingError::InvalidLeverage);
+        }
+
+        self.accrue_interest();
+        let borrow_amount = self.leverage_borrow(initial_amount, target_leverage_bps);
+        if borrow_amount > self.get_available_liquidity() {
+            self.env().revert(LendingError::InsufficientLiquidity);
+        }
+        if borrow_amount > U512::zero() {
+            self.add_debt(caller, borrow_amount);
+        }
+
+        // Stake everything at once; the pool holds the thCSPR as collateral
+        let total_staked = initial_amount + borrow_amount;
+        let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+        let thcspr_received = ThawCoreContractRef::new(self.env(), thaw_core)
+            .with_tokens(total_staked)
+            .stake();
+        if thcspr_received < min_thcspr_out {
+            self.env().revert(LendingError::SlippageExceeded);
+        }
+        let current_collateral = self.collateral_balances.get(&caller).unwrap_or_default();
+        self.collateral_balances.set(&caller, current_collateral + thcspr_received);
+
+        // Earlier debt counts too, so check the whole position
+        if self.get_health_factor(caller) < U512::from(PRECISION) {
+            self.env().revert(LendingError::WouldBecomeUndercollateralized);
         }
 
         self.env().emit_event(LeveragedStake {
             user: caller,
             initial_amount,
             total_staked,
-            total_thcspr,
-            leverage_loops: loops,
+            total_thcspr: thcspr_received,
+            total_borrowed: borrow_amount,
+            leverage_loops: 1,
         });
 
-        total_thcspr
+        thcspr_received
+    }
+
+    // ============ DELEVERAGING ============
+
+    /// Sell collateral to bring the caller's position down to `target_leverage_bps`
+    ///
+    /// Leverage is collateral value over equity (value less debt), so 10000
+    /// repays all debt. See `close_position` for how the thCSPR is sold. The
+    /// instant unstake fee comes out of equity, leaving the position slightly
+    /// below the target.
+    ///
+    /// # Returns
+    /// CSPR sent to the caller, zero if the unwind was queued
+    pub fn deleverage(&mut self, target_leverage_bps: u64) -> U512 {
+        let caller = self.env().caller();
+        self.require_no_unwind(caller);
+
+        self.accrue_interest();
+        let collateral = self.collateral_balances.get(&caller).unwrap_or_default();
+        let debt = self.debt_of(caller);
+        if debt == U512::zero() {
+            self.env().revert(LendingError::NothingToUnwind);
+        }
+        let collateral_value = self.get_collateral_value(collateral);
+        if collateral_value <= debt {
+            self.env().revert(LendingError::InsufficientCollateral);
+        }
+
+        // Selling x repays x, so (value - x) / (value - debt) = target
+        let target_value = (collateral_value - debt) * U512::from(target_leverage_bps)
+            / U512::from(BPS_PRECISION);
+        if target_leverage_bps < BPS_PRECISION || target_value >= collateral_value {
+            self.env().revert(LendingError::InvalidLeverage);
+        }
+        let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+        let thcspr_amount = ThawCoreContractRef::new(self.env(), thaw_core)
+            .preview_withdraw(collateral_value - target_value)
+            .min(collateral);
+        if thcspr_amount == U512::zero() {
+            self.env().revert(LendingError::InvalidLeverage);
+        }
+
+        self.unwind(caller, thcspr_amount)
+    }
+
+    /// Sell all of the caller's collateral, repay the debt and return the rest
+    ///
+    /// The thCSPR is unstaked instantly through the ThawCore liquidity buffer
+    /// when it covers the amount. Otherwise it goes through the withdrawal
+    /// queue and keeps counting as collateral until `settle_unwind` claims it.
+    /// An instant close reverts if the proceeds fall short of the debt, or if
+    /// it would send CSPR to the caller while withdrawals are paused.
+    ///
+    /// # Returns
+    /// CSPR sent to the caller, zero if the unwind was queued
+    pub fn close_position(&mut self) -> U512 {
+        let caller = self.env().caller();
+        self.require_no_unwind(caller);
+
+        let collateral = self.collateral_balances.get(&caller).unwrap_or_default();
+        if collateral == U512::zero() {
+            self.env().revert(LendingError::NothingToUnwind);
+        }
+
+        self.accrue_interest();
+        self.unwind(caller, collateral)
+    }
+
+    /// Claim a queued unwind once it has unbonded and pay down the position
+    ///
+    /// Anyone can settle; what is left after repaying goes to `user`, so it
+    /// reverts while withdrawals are paused unless the claim only repays
+    /// debt. Debt the claim cannot cover on a position without collateral is
+    /// bad debt.
+    ///
+    /// # Returns
+    /// CSPR sent to `user`
+    pub fn settle_unwind(&mut self, user: Address) -> U512 {
+        let unwind = self
+            .get_pending_unwind(user)
+            .unwrap_or_revert_with(&self.env(), LendingError::NoUnwindPending);
+        self.pending_unwinds.set(&user, None);
+
+        let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+        let cspr_received =
+            ThawCoreContractRef::new(self.env(), thaw_core).claim(unwind.withdrawal_id);
+
+        self.accrue_interest();
+        let returned = self.repay_from_proceeds(user, unwind.thcspr_amount, cspr_received);
+        if self.collateral_balances.get(&user).unwrap_or_default() == U512::zero() {
+            self.cover_bad_debt(user);
+        }
+        returned
     }
 
     // ============ LIQUIDATION ============
@@ -330,16 +644,18 @@ impl LendingPool {
     /// Liquidate an undercollateralized position
     #[odra(payable)]
     pub fn liquidate(&mut self, borrower: Address) {
+        self.require_not_paused(LendingAction::Liquidate);
         let caller = self.env().caller();
         let repay_amount = self.env().attached_value();
 
         // Check position is liquidatable
+        self.accrue_interest();
         let health = self.get_health_factor(borrower);
         if health >= U512::from(PRECISION) {
             self.env().revert(LendingError::PositionHealthy);
         }
 
-        let borrowed = self.borrowed_balances.get(&borrower).unwrap_or_default();
+        let borrowed = self.debt_of(borrower);
         let collateral = self.collateral_balances.get(&borrower).unwrap_or_default();
 
         // Can repay up to 50% of debt
@@ -365,10 +681,11 @@ impl LendingPool {
 
         // Update borrower state
         self.collateral_balances.set(&borrower, collateral - seize_amount);
-        self.borrowed_balances.set(&borrower, borrowed - actual_repay);
-
-        let new_total_borrowed = self.total_borrowed.get_or_default() - actual_repay;
-        self.total_borrowed.set(new_total_borrowed);
+        self.reduce_debt(borrower, actual_repay);
+        // A queued unwind will still pay down what is left
+        if seize_amount == collateral && self.get_pending_unwind(borrower).is_none() {
+            self.cover_bad_debt(borrower);
+        }
 
         // Transfer collateral to liquidator
         let thcspr = self.thcspr_token.get().unwrap_or_revert(&self.env());
@@ -387,58 +704,242 @@ impl LendingPool {
 
     /// Get available liquidity for borrowing
     pub fn get_available_liquidity(&self) -> U512 {
-        let deposits = self.total_deposits.get_or_default();
+        // Accrued interest raises both sides alike, so this is the idle CSPR
+        let assets = self.total_deposits.get_or_default() + self.total_reserves.get_or_default();
         let borrowed = self.total_borrowed.get_or_default();
-        if deposits > borrowed { deposits - borrowed } else { U512::zero() }
+        if assets > borrowed { assets - borrowed } else { U512::zero() }
     }
 
     /// Get user's health factor (1e18 = healthy, below = liquidatable)
     pub fn get_health_factor(&self, user: Address) -> U512 {
         let collateral = self.collateral_balances.get(&user).unwrap_or_default();
-        let borrowed = self.borrowed_balances.get(&user).unwrap_or_default();
+        let borrowed = self.debt_of(user);
 
         if borrowed == U512::zero() {
             return U512::MAX;
         }
 
-        let collateral_value = self.get_collateral_value(collateral);
-        let liq_threshold = self.get_liquidation_threshold();
+        self.health_factor_of(self.position_value(user, collateral), borrowed)
+    }
 
-        collateral_value * U512::from(liq_threshold) * U512::from(PRECISION)
-            / (borrowed * U512::from(BPS_PRECISION))
+    /// Get the highest target leverage the collateral factor allows (basis points)
+    ///
+    /// Borrowing `L - 1` against a position worth `L` needs
+    /// `(L - 1) / L <= collateral factor`, so 75% allows 4x.
+    pub fn get_max_leverage(&self) -> u64 {
+        let collateral_factor = self.get_collateral_factor().min(BPS_PRECISION - 1);
+        BPS_PRECISION * BPS_PRECISION / (BPS_PRECISION - collateral_factor)
+    }
+
+    /// Preview a fresh position from staking `amount` CSPR at `target_leverage_bps`
+    pub fn preview_leverage(&self, amount: U512, target_leverage_bps: u64) -> LeveragePreview {
+        if target_leverage_bps < BPS_PRECISION || target_leverage_bps > self.get_max_leverage() {
+            self.env().revert(LendingError::InvalidLeverage);
+        }
+
+        let debt = self.leverage_borrow(amount, target_leverage_bps);
+        let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+        let collateral =
+            ThawCoreContractRef::new(self.env(), thaw_core).preview_stake(amount + debt);
+        let health_factor = self.health_factor_of(self.get_collateral_value(collateral), debt);
+
+        let utilization = interest_rate::utilization_bps(
+            self.get_total_borrowed() + debt,
+            self.get_total_deposits(),
+        );
+        let borrow_apr_bps = self.get_borrow_rate_at(utilization);
+        let staking_yield = self.get_staking_apr() as i64 * target_leverage_bps as i64;
+        let borrow_cost =
+            borrow_apr_bps as i64 * (target_leverage_bps - BPS_PRECISION) as i64;
+
+        LeveragePreview {
+            collateral,
+            debt,
+            health_factor,
+            borrow_apr_bps,
+            net_apr_bps: (staking_yield - borrow_cost) / BPS_PRECISION as i64,
+        }
+    }
+
+    /// Get the staking APR assumed by leverage previews (basis points)
+    pub fn get_staking_apr(&self) -> u64 {
+        self.staking_apr_bps.get_or_default()
     }
 
     /// Get maximum additional borrow for user
     pub fn get_max_borrow(&self, user: Address) -> U512 {
         let collateral = self.collateral_balances.get(&user).unwrap_or_default();
-        let collateral_value = self.get_collateral_value(collateral);
+        let collateral_value = self.position_value(user, collateral);
         let max_total = self.calculate_max_borrow(collateral_value);
 
-        let current = self.borrowed_balances.get(&user).unwrap_or_default();
+        let current = self.debt_of(user);
         if max_total > current { max_total - current } else { U512::zero() }
     }
 
-    /// Get user's position: (collateral, borrowed)
+    /// Get user's position: (collateral, borrowed including interest)
     pub fn get_position(&self, user: Address) -> (U512, U512) {
         (
             self.collateral_balances.get(&user).unwrap_or_default(),
-            self.borrowed_balances.get(&user).unwrap_or_default(),
+            self.debt_of(user),
         )
     }
 
-    /// Get total deposits in pool
+    /// Get the user's unwind waiting in the ThawCore withdrawal queue, if any
+    pub fn get_pending_unwind(&self, user: Address) -> Option<PendingUnwind> {
+        self.pending_unwinds.get(&user).flatten()
+    }
+
+    /// Get total deposits in pool, including interest earned by lenders
     pub fn get_total_deposits(&self) -> U512 {
-        self.total_deposits.get_or_default()
+        let interest = self.pending_interest();
+        self.total_deposits.get_or_default() + interest - self.reserve_part(interest)
     }
 
-    /// Get total borrowed from pool
+    /// Get total borrowed from pool, including accrued interest
     pub fn get_total_borrowed(&self) -> U512 {
-        self.total_borrowed.get_or_default()
+        self.total_borrowed.get_or_default() + self.pending_interest()
     }
 
-    /// Get lender deposit amount
+    /// Get lender balance: CSPR value of the user's tCSPR
     pub fn get_lender_deposit(&self, user: Address) -> U512 {
-        self.lender_deposits.get(&user).unwrap_or_default()
+        let shares = self.lender_shares_of(user);
+        let total_shares = self.total_lender_shares.get_or_default();
+        share_math::to_assets(shares, self.get_total_deposits(), total_shares, Rounding::Down)
+    }
+
+    /// Get the tCSPR token address
+    pub fn get_lender_token(&self) -> Option<Address> {
+        self.lender_token.get()
+    }
+
+    /// Get the CSPR value of one tCSPR (1e18 precision)
+    pub fn get_lender_exchange_rate(&self) -> U512 {
+        share_math::to_assets(
+            U512::from(PRECISION),
+            self.get_total_deposits(),
+            self.total_lender_shares.get_or_default(),
+            Rounding::Down,
+        )
+    }
+
+    /// Get the user's average tCSPR entry rate (1e18 precision)
+    ///
+    /// tCSPR received by transfer is costed at this rate, or at 1:1 for
+    /// holders that never deposited.
+    pub fn get_lender_entry_rate(&self, user: Address) -> U512 {
+        self.entry_rate_of(user)
+    }
+
+    /// Get the current borrow index (1e18 at launch, grows with interest)
+    pub fn get_borrow_index(&self) -> U512 {
+        let index = self.stored_borrow_index();
+        let growth = self.pending_growth();
+        index + share_math::mul_div(index, growth, U512::from(PRECISION), Rounding::Down)
+    }
+
+    /// Get the current pool utilization: borrowed / deposits (basis points)
+    pub fn get_utilization(&self) -> u64 {
+        interest_rate::utilization_bps(
+            self.total_borrowed.get_or_default(),
+            self.total_deposits.get_or_default(),
+        )
+    }
+
+    /// Get the current borrow APR (basis points)
+    pub fn get_borrow_apr(&self) -> u64 {
+        self.get_borrow_rate_at(self.get_utilization())
+    }
+
+    /// Get the current supply APR: borrow APR scaled by utilization (basis points)
+    ///
+    /// Net of the reserve factor.
+    pub fn get_supply_apr(&self) -> u64 {
+        let lender_share = BPS_PRECISION - self.get_reserve_factor();
+        self.get_borrow_apr() * self.get_utilization() * lender_share
+            / (BPS_PRECISION * BPS_PRECISION)
+    }
+
+    /// Get the share of borrow interest kept as protocol reserves (basis points)
+    pub fn get_reserve_factor(&self) -> u64 {
+        self.reserve_factor_bps.get_or_default()
+    }
+
+    /// Get the protocol reserves, including the share of pending interest
+    pub fn get_reserves(&self) -> U512 {
+        self.total_reserves.get_or_default() + self.reserve_part(self.pending_interest())
+    }
+
+    /// Get the interest rate model parameters
+    pub fn get_interest_rate_model(&self) -> InterestRateModel {
+        self.rate_model.get().unwrap_or(InterestRateModel::DEFAULT)
+    }
+
+    /// Get the borrow APR (basis points) the model gives at any utilization
+    pub fn get_borrow_rate_at(&self, utilization_bps: u64) -> u64 {
+        self.get_interest_rate_model().borrow_rate(utilization_bps)
+    }
+
+    /// Get the interest reserve recipients
+    pub fn get_fee_recipients(&self) -> Vec<FeeRecipient> {
+        self.fee_splitter.get_recipients()
+    }
+
+    /// Get the total reserves withdrawn to `account` so far
+    pub fn get_fee_accrued(&self, account: Address) -> U512 {
+        self.fee_splitter.get_accrued(account)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(&self) -> Option<Address> {
+        self.admin.get()
+    }
+
+    /// Get the proposed admin waiting to accept, if any
+    pub fn get_pending_admin(&self) -> Option<Address> {
+        self.pending_admin.get().flatten()
+    }
+
+    /// Check whether `account` has been granted `role`
+    pub fn has_role(&self, role: Role, account: Address) -> bool {
+        self.roles.has_role(role, account)
+    }
+
+    /// Get the pause flag of every lending action
+    pub fn get_pause_state(&self) -> LendingPauseState {
+        self.pause_state.get().unwrap_or(LendingPauseState {
+            deposit: false,
+            withdraw: false,
+            borrow: false,
+            leverage: false,
+            liquidate: false,
+        })
+    }
+
+    /// Check whether `action` is paused
+    pub fn is_action_paused(&self, action: LendingAction) -> bool {
+        let state = self.get_pause_state();
+        match action {
+            LendingAction::Deposit => state.deposit,
+            LendingAction::Withdraw => state.withdraw,
+            LendingAction::Borrow => state.borrow,
+            LendingAction::Leverage => state.leverage,
+            LendingAction::Liquidate => state.liquidate,
+        }
+    }
+
+    /// Get the delay before queued config changes can execute (zero = instant)
+    pub fn get_timelock_delay(&self) -> u64 {
+        self.timelock.get_delay()
+    }
+
+    /// Get a queued config change by id
+    pub fn get_queued_operation(&self, id: u64) -> Option<QueuedOperation> {
+        self.timelock.get_operation(id)
+    }
+
+    /// Get all config changes waiting for execution
+    pub fn get_queued_operations(&self) -> Vec<QueuedOperation> {
+        self.timelock.get_queued_operations()
     }
 
     // ============ CONFIG HELPERS ============
@@ -460,12 +961,262 @@ impl LendingPool {
 
     // ============ INTERNAL FUNCTIONS ============
 
+    /// Unstake `thcspr_amount` of `user`'s collateral to pay down their debt
+    /// (interest must be accrued)
+    fn unwind(&mut self, user: Address, thcspr_amount: U512) -> U512 {
+        let collateral = self.collateral_balances.get(&user).unwrap_or_default();
+        self.collateral_balances.set(&user, collateral - thcspr_amount);
+
+        // ThawCore caps the instant fee itself, and queues the request when
+        // its buffer cannot cover it
+        let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+        let outcome = ThawCoreContractRef::new(self.env(), thaw_core)
+            .instant_unstake(thcspr_amount, BPS_PRECISION);
+        if let Some(withdrawal_id) = outcome.withdrawal_id {
+            self.pending_unwinds.set(
+                &user,
+                Some(PendingUnwind {
+                    withdrawal_id,
+                    thcspr_amount,
+                }),
+            );
+            self.env().emit_event(UnwindQueued {
+                user,
+                withdrawal_id,
+                thcspr_sold: thcspr_amount,
+            });
+            return U512::zero();
+        }
+
+        let returned = self.repay_from_proceeds(user, thcspr_amount, outcome.cspr_amount);
+        if self.get_health_factor(user) < U512::from(PRECISION) {
+            self.env().revert(LendingError::WouldBecomeUndercollateralized);
+        }
+        returned
+    }
+
+    /// Take `amount` out of the reserves and send it to `payouts`
+    fn pay_reserves(&mut self, amount: U512, payouts: Vec<(Address, U512)>) {
+        self.accrue_interest();
+        let reserves = self.total_reserves.get_or_default();
+        if amount > reserves {
+            self.env().revert(LendingError::InsufficientReserves);
+        }
+        if amount > self.get_available_liquidity() {
+            self.env().revert(LendingError::InsufficientLiquidity);
+        }
+
+        self.total_reserves.set(reserves - amount);
+        for (recipient, part) in payouts {
+            if part == U512::zero() {
+                continue;
+            }
+            self.fee_splitter.accrue(recipient, part);
+            self.env().transfer_tokens(&recipient, &part);
+
+            self.env().emit_event(ReservesWithdrawn {
+                to: recipient,
+                amount: part,
+                remaining_reserves: reserves - amount,
+            });
+        }
+    }
+
+    /// Repay `user`'s debt out of unstaked CSPR and send them the rest
+    ///
+    /// Repaying stays open while withdrawals are paused, sending the rest
+    /// does not.
+    fn repay_from_proceeds(
+        &mut self,
+        user: Address,
+        thcspr_sold: U512,
+        cspr_received: U512,
+    ) -> U512 {
+        let debt_repaid = cspr_received.min(self.debt_of(user));
+        self.reduce_debt(user, debt_repaid);
+
+        let returned = cspr_received - debt_repaid;
+        if returned > U512::zero() {
+            self.require_not_paused(LendingAction::Withdraw);
+            self.env().transfer_tokens(&user, &returned);
+        }
+
+        self.env().emit_event(PositionUnwound {
+            user,
+            thcspr_sold,
+            cspr_received,
+            debt_repaid,
+            returned,
+        });
+        returned
+    }
+
+    fn require_no_unwind(&self, user: Address) {
+        if self.get_pending_unwind(user).is_some() {
+            self.env().revert(LendingError::UnwindPending);
+        }
+    }
+
+    /// Collateral value of `user`'s position, including any queued unwind
+    fn position_value(&self, user: Address, collateral: U512) -> U512 {
+        let unwind_value = match self.get_pending_unwind(user) {
+            Some(unwind) => {
+                let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
+                ThawCoreContractRef::new(self.env(), thaw_core)
+                    .get_withdrawal_payout(unwind.withdrawal_id)
+                    .unwrap_or_default()
+            }
+            None => U512::zero(),
+        };
+        self.get_collateral_value(collateral) + unwind_value
+    }
+
+    /// Accrue interest since the last accrual into the borrow index and totals
+    fn accrue_interest(&mut self) {
+        let interest = self.pending_interest();
+        let index = self.get_borrow_index();
+        self.last_accrual_timestamp.set(self.env().get_block_time());
+        if interest == U512::zero() {
+            return;
+        }
+        self.borrow_index.set(index);
+        let borrowed = self.total_borrowed.get_or_default();
+        self.total_borrowed.set(borrowed + interest);
+
+        let to_reserves = self.reserve_part(interest);
+        let reserves = self.total_reserves.get_or_default();
+        self.total_reserves.set(reserves + to_reserves);
+        let deposits = self.total_deposits.get_or_default();
+        self.total_deposits.set(deposits + interest - to_reserves);
+    }
+
+    /// Protocol share of `interest`
+    fn reserve_part(&self, interest: U512) -> U512 {
+        interest * U512::from(self.get_reserve_factor()) / U512::from(BPS_PRECISION)
+    }
+
+    /// Write off the debt of a borrower left without collateral
+    ///
+    /// Reserves absorb the loss first, lenders take the rest through a lower
+    /// tCSPR exchange rate.
+    fn cover_bad_debt(&mut self, borrower: Address) {
+        let bad_debt = self.debt_of(borrower);
+        if bad_debt == U512::zero() {
+            return;
+        }
+        self.reduce_debt(borrower, bad_debt);
+
+        let reserves = self.total_reserves.get_or_default();
+        let covered_by_reserves = reserves.min(bad_debt);
+        self.total_reserves.set(reserves - covered_by_reserves);
+
+        let deposits = self.total_deposits.get_or_default();
+        let lender_loss = (bad_debt - covered_by_reserves).min(deposits);
+        self.total_deposits.set(deposits - lender_loss);
+
+        self.env().emit_event(BadDebtCovered {
+            borrower,
+            bad_debt,
+            covered_by_reserves,
+            lender_loss,
+        });
+    }
+
+    fn stored_borrow_index(&self) -> U512 {
+        self.borrow_index.get().unwrap_or(U512::from(PRECISION))
+    }
+
+    /// Relative index growth since the last accrual (1e18 = 100%)
+    fn pending_growth(&self) -> U512 {
+        let now = self.env().get_block_time();
+        let elapsed = now.saturating_sub(self.last_accrual_timestamp.get_or_default());
+        if elapsed == 0 || self.total_borrowed.get_or_default() == U512::zero() {
+            return U512::zero();
+        }
+        U512::from(PRECISION) * U512::from(self.get_borrow_apr()) * U512::from(elapsed)
+            / (U512::from(BPS_PRECISION) * U512::from(MS_PER_YEAR))
+    }
+
+    /// Interest owed on `total_borrowed` since the last accrual
+    fn pending_interest(&self) -> U512 {
+        let borrowed = self.total_borrowed.get_or_default();
+        share_math::mul_div(borrowed, self.pending_growth(), U512::from(PRECISION), Rounding::Down)
+    }
+
+    fn lender_token_ref(&self) -> TCsprTokenContractRef {
+        let token = self
+            .lender_token
+            .get()
+            .unwrap_or_revert_with(&self.env(), LendingError::LenderTokenNotSet);
+        TCsprTokenContractRef::new(self.env(), token)
+    }
+
+    /// tCSPR held by `user` (zero before the token is set)
+    fn lender_shares_of(&self, user: Address) -> U512 {
+        match self.lender_token.get() {
+            Some(token) => {
+                let balance = TCsprTokenContractRef::new(self.env(), token).balance_of(user);
+                U512::from(balance.as_u128())
+            }
+            None => U512::zero(),
+        }
+    }
+
+    fn entry_rate_of(&self, user: Address) -> U512 {
+        self.lender_entry_rates.get(&user).unwrap_or(U512::from(PRECISION))
+    }
+
+    /// Current debt of `user` including interest (rounds up)
+    fn debt_of(&self, user: Address) -> U512 {
+        let scaled = self.scaled_debt.get(&user).unwrap_or_default();
+        share_math::mul_div(scaled, self.get_borrow_index(), U512::from(PRECISION), Rounding::Up)
+    }
+
+    /// Record `amount` of new debt for `user` (interest must be accrued)
+    fn add_debt(&mut self, user: Address, amount: U512) {
+        let index = self.stored_borrow_index();
+        let scaled = share_math::mul_div(amount, U512::from(PRECISION), index, Rounding::Up);
+        let current = self.scaled_debt.get(&user).unwrap_or_default();
+        self.scaled_debt.set(&user, current + scaled);
+        let total = self.total_borrowed.get_or_default();
+        self.total_borrowed.set(total + amount);
+    }
+
+    /// Remove `amount` of debt from `user` (interest must be accrued)
+    fn reduce_debt(&mut self, user: Address, amount: U512) {
+        let current = self.scaled_debt.get(&user).unwrap_or_default();
+        let remaining = if amount >= self.debt_of(user) {
+            U512::zero()
+        } else {
+            let index = self.stored_borrow_index();
+            let scaled = share_math::mul_div(amount, U512::from(PRECISION), index, Rounding::Down);
+            current.saturating_sub(scaled)
+        };
+        self.scaled_debt.set(&user, remaining);
+        let total = self.total_borrowed.get_or_default();
+        self.total_borrowed.set(total.saturating_sub(amount));
+    }
+
     fn get_collateral_value(&self, thcspr_amount: U512) -> U512 {
         let thaw_core = self.thaw_core.get().unwrap_or_revert(&self.env());
         let exchange_rate = ThawCoreContractRef::new(self.env(), thaw_core).get_exchange_rate();
         thcspr_amount * exchange_rate / U512::from(PRECISION)
     }
 
+    fn health_factor_of(&self, collateral_value: U512, borrowed: U512) -> U512 {
+        if borrowed == U512::zero() {
+            return U512::MAX;
+        }
+        let liq_threshold = self.get_liquidation_threshold();
+        collateral_value * U512::from(liq_threshold) * U512::from(PRECISION)
+            / (borrowed * U512::from(BPS_PRECISION))
+    }
+
+    /// CSPR to borrow so that `amount` ends up levered to `target_leverage_bps`
+    fn leverage_borrow(&self, amount: U512, target_leverage_bps: u64) -> U512 {
+        amount * U512::from(target_leverage_bps - BPS_PRECISION) / U512::from(BPS_PRECISION)
+    }
+
     fn calculate_max_borrow(&self, collateral_value: U512) -> U512 {
         let factor = self.get_collateral_factor();
         collateral_value * U512::from(factor) / U512::from(BPS_PRECISION)
@@ -479,12 +1230,229 @@ impl LendingPool {
 
     // ============ ADMIN ============
 
+    /// Pause or unpause a single lending action (pauser)
+    ///
+    /// Takes effect immediately, it does not go through the timelock.
+    pub fn set_action_paused(&mut self, action: LendingAction, paused: bool) {
+        self.require_role(PAUSER_ROLE);
+        let mut state = self.get_pause_state();
+        match action {
+            LendingAction::Deposit => state.deposit = paused,
+            LendingAction::Withdraw => state.withdraw = paused,
+            LendingAction::Borrow => state.borrow = paused,
+            LendingAction::Leverage => state.leverage = paused,
+            LendingAction::Liquidate => state.liquidate = paused,
+        }
+        self.pause_state.set(state);
+        self.env().emit_event(LendingActionPauseUpdated {
+            action,
+            paused,
+            by: self.env().caller(),
+        });
+    }
+
+    /// Update the risk config (risk manager, goes through the timelock)
     pub fn set_config(&mut self, collateral_factor: u64, liq_threshold: u64, liq_bonus: u64) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetLendingConfig {
+            collateral_factor,
+            liq_threshold,
+            liq_bonus,
+        });
+    }
+
+    /// Update the interest rate model (risk manager, goes through the timelock)
+    pub fn set_interest_rate_model(
+        &mut self,
+        base_rate_bps: u64,
+        slope1_bps: u64,
+        optimal_utilization_bps: u64,
+        slope2_bps: u64,
+    ) {
+        self.require_role(RISK_MANAGER_ROLE);
+        let model = InterestRateModel {
+            base_rate_bps,
+            slope1_bps,
+            optimal_utilization_bps,
+            slope2_bps,
+        };
+        if !model.is_valid() {
+            self.env().revert(LendingError::InvalidRateModel);
+        }
+        self.schedule(TimelockedCall::SetInterestRateModel {
+            base_rate_bps,
+            slope1_bps,
+            optimal_utilization_bps,
+            slope2_bps,
+        });
+    }
+
+    /// Set the timelock delay for config changes (admin only)
+    pub fn set_timelock_delay(&mut self, delay_ms: u64) {
         self.require_admin();
-        let config = U512::from(collateral_factor)
-            | (U512::from(liq_threshold) << 16)
-            | (U512::from(liq_bonus) << 32);
-        self.config.set(config);
+        if delay_ms > MAX_TIMELOCK_DELAY_MS {
+            self.env().revert(LendingError::InvalidTimelockDelay);
+        }
+        self.schedule(TimelockedCall::SetTimelockDelay { delay_ms });
+    }
+
+    /// Execute a queued config change once its ETA has passed (anyone)
+    pub fn execute_operation(&mut self, id: u64) {
+        let operation = self
+            .timelock
+            .get_operation(id)
+            .unwrap_or_revert_with(&self.env(), LendingError::OperationNotQueued);
+        if self.env().get_block_time() < operation.eta {
+            self.env().revert(LendingError::TimelockNotReady);
+        }
+        self.timelock.dequeue(id);
+        self.env().emit_event(OperationExecuted { id });
+        self.apply_call(operation.call);
+    }
+
+    /// Cancel a queued config change (same permission as queuing it)
+    pub fn cancel_operation(&mut self, id: u64) {
+        let operation = self
+            .timelock
+            .get_operation(id)
+            .unwrap_or_revert_with(&self.env(), LendingError::OperationNotQueued);
+        match operation.call {
+            TimelockedCall::SetLendingConfig { .. }
+            | TimelockedCall::SetInterestRateModel { .. }
+            | TimelockedCall::SetStakingApr { .. } => self.require_role(RISK_MANAGER_ROLE),
+            TimelockedCall::SetReserveFactor { .. } | TimelockedCall::SetFeeRecipients { .. } => {
+                self.require_role(FEE_MANAGER_ROLE)
+            }
+            _ => self.require_admin(),
+        }
+        self.timelock.dequeue(id);
+        self.env().emit_event(OperationCancelled { id });
+    }
+
+    /// Set the staking APR used by `preview_leverage` (risk manager)
+    ///
+    /// Goes through the timelock.
+    pub fn set_staking_apr(&mut self, apr_bps: u64) {
+        self.require_role(RISK_MANAGER_ROLE);
+        if apr_bps > BPS_PRECISION {
+            self.env().revert(LendingError::InvalidParameter);
+        }
+        self.schedule(TimelockedCall::SetStakingApr { apr_bps });
+    }
+
+    /// Update the reserve factor (fee manager, goes through the timelock)
+    pub fn set_reserve_factor(&mut self, reserve_factor_bps: u64) {
+        self.require_role(FEE_MANAGER_ROLE);
+        if reserve_factor_bps > MAX_RESERVE_FACTOR_BPS {
+            self.env().revert(LendingError::InvalidReserveFactor);
+        }
+        self.schedule(TimelockedCall::SetReserveFactor { reserve_factor_bps });
+    }
+
+    /// Withdraw protocol reserves to `to` (admin only)
+    ///
+    /// Goes through the timelock; reserves and liquidity are checked when the
+    /// withdrawal is applied.
+    pub fn withdraw_reserves(&mut self, to: Address, amount: U512) {
+        self.require_admin();
+        if amount == U512::zero() {
+            self.env().revert(LendingError::AmountMustBePositive);
+        }
+        self.schedule(TimelockedCall::WithdrawReserves { to, amount });
+    }
+
+    /// Split protocol reserves between the fee recipients (admin only)
+    ///
+    /// The recipients are set through the timelock with `set_fee_recipients`,
+    /// so this pays out immediately.
+    pub fn distribute_reserves(&mut self, amount: U512) {
+        self.require_admin();
+        if amount == U512::zero() {
+            self.env().revert(LendingError::AmountMustBePositive);
+        }
+        let payouts = self.fee_splitter.split(amount);
+        if payouts.is_empty() {
+            self.env().revert(LendingError::FeeRecipientsNotSet);
+        }
+        self.pay_reserves(amount, payouts);
+    }
+
+    /// Set the tCSPR token once it is deployed with this pool as minter (admin only)
+    pub fn set_lender_token(&mut self, lender_token: Address) {
+        self.require_admin();
+        if self.lender_token.get().is_some() {
+            self.env().revert(LendingError::LenderTokenAlreadySet);
+        }
+        self.lender_token.set(lender_token);
+    }
+
+    /// Set the interest reserve recipients and their shares (fee manager)
+    ///
+    /// Goes through the timelock.
+    pub fn set_fee_recipients(&mut self, recipients: Vec<FeeRecipient>) {
+        self.require_role(FEE_MANAGER_ROLE);
+        if !is_valid_split(&recipients) {
+            self.env().revert(LendingError::InvalidFeeRecipients);
+        }
+        self.schedule(TimelockedCall::SetFeeRecipients { recipients });
+    }
+
+    /// Propose a new admin (admin only); takes effect once accepted
+    pub fn propose_admin(&mut self, new_admin: Address) {
+        self.require_admin();
+        self.pending_admin.set(Some(new_admin));
+        self.env().emit_event(AdminTransferProposed {
+            current_admin: self.env().caller(),
+            pending_admin: new_admin,
+        });
+    }
+
+    /// Accept a pending admin transfer (proposed admin only)
+    pub fn accept_admin(&mut self) {
+        let new_admin = self
+            .get_pending_admin()
+            .unwrap_or_revert_with(&self.env(), LendingError::NoPendingAdmin);
+        if self.env().caller() != new_admin {
+            self.env().revert(LendingError::NotPendingAdmin);
+        }
+        let old_admin = self.admin.get().unwrap_or_revert(&self.env());
+        self.admin.set(new_admin);
+        self.pending_admin.set(None);
+        self.env().emit_event(AdminTransferred {
+            old_admin,
+            new_admin,
+        });
+    }
+
+    /// Cancel a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(&mut self) {
+        self.require_admin();
+        let cancelled_admin = self
+            .get_pending_admin()
+            .unwrap_or_revert_with(&self.env(), LendingError::NoPendingAdmin);
+        self.pending_admin.set(None);
+        self.env().emit_event(AdminTransferCancelled { cancelled_admin });
+    }
+
+    /// Grant one of the protocol roles to `account` (admin only)
+    pub fn grant_role(&mut self, role: Role, account: Address) {
+        self.require_admin();
+        if !is_known_role(&role) {
+            self.env().revert(LendingError::InvalidRole);
+        }
+        self.roles.grant(role, account);
+    }
+
+    /// Revoke a role from `account` (admin only)
+    pub fn revoke_role(&mut self, role: Role, account: Address) {
+        self.require_admin();
+        self.roles.revoke(role, account);
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: Role) {
+        let caller = self.env().caller();
+        self.roles.revoke(role, caller);
     }
 
     fn require_admin(&self) {
@@ -493,4 +1461,124 @@ impl LendingPool {
             self.env().revert(LendingError::NotAdmin);
         }
     }
+
+    /// Apply `call` now if there is no timelock delay, otherwise queue it
+    fn schedule(&mut self, call: TimelockedCall) {
+        if self.timelock.get_delay() == 0 {
+            self.apply_call(call);
+            return;
+        }
+        let now = self.env().get_block_time();
+        let operation = self.timelock.queue(call, now);
+        self.env().emit_event(OperationQueued {
+            id: operation.id,
+            eta: operation.eta,
+        });
+    }
+
+    /// Apply a timelocked config change
+    fn apply_call(&mut self, call: TimelockedCall) {
+        match call {
+            TimelockedCall::SetLendingConfig {
+                collateral_factor,
+                liq_threshold,
+                liq_bonus,
+            } => {
+                let config = U512::from(collateral_factor)
+                    | (U512::from(liq_threshold) << 16)
+                    | (U512::from(liq_bonus) << 32);
+                self.config.set(config);
+            }
+            TimelockedCall::SetInterestRateModel {
+                base_rate_bps,
+                slope1_bps,
+                optimal_utilization_bps,
+                slope2_bps,
+            } => {
+                // Interest up to now accrues at the old rate
+                self.accrue_interest();
+                self.rate_model.set(InterestRateModel {
+                    base_rate_bps,
+                    slope1_bps,
+                    optimal_utilization_bps,
+                    slope2_bps,
+                });
+                self.env().emit_event(InterestRateModelUpdated {
+                    base_rate_bps,
+                    slope1_bps,
+                    optimal_utilization_bps,
+                    slope2_bps,
+                });
+            }
+            TimelockedCall::SetReserveFactor { reserve_factor_bps } => {
+                // Interest up to now is split at the old factor
+                self.accrue_interest();
+                let old_reserve_factor_bps = self.get_reserve_factor();
+                self.reserve_factor_bps.set(reserve_factor_bps);
+                self.env().emit_event(ReserveFactorUpdated {
+                    old_reserve_factor_bps,
+                    new_reserve_factor_bps: reserve_factor_bps,
+                });
+            }
+            TimelockedCall::SetStakingApr { apr_bps } => {
+                let old_apr_bps = self.get_staking_apr();
+                self.staking_apr_bps.set(apr_bps);
+                self.env().emit_event(StakingAprUpdated {
+                    old_apr_bps,
+                    new_apr_bps: apr_bps,
+                });
+            }
+            TimelockedCall::SetFeeRecipients { recipients } => {
+                self.env().emit_event(FeeRecipientsUpdated {
+                    accounts: recipients.iter().map(|r| r.account).collect(),
+                    shares_bps: recipients.iter().map(|r| r.share_bps).collect(),
+                });
+                self.fee_splitter.set_recipients(recipients);
+            }
+            TimelockedCall::WithdrawReserves { to, amount } => {
+                self.pay_reserves(amount, vec![(to, amount)]);
+            }
+            TimelockedCall::SetTimelockDelay { delay_ms } => {
+                let old_delay_ms = self.timelock.get_delay();
+                self.timelock.set_delay(delay_ms);
+                self.env().emit_event(TimelockDelayUpdated {
+                    old_delay_ms,
+                    new_delay_ms: delay_ms,
+                });
+            }
+            TimelockedCall::SetProtocolFee { .. }
+            | TimelockedCall::SetValidator { .. }
+            | TimelockedCall::StartValidatorMigration { .. }
+            | TimelockedCall::AddValidator { .. }
+            | TimelockedCall::RemoveValidator { .. }
+            | TimelockedCall::SetValidatorWeight { .. }
+            | TimelockedCall::SetThcsprToken { .. }
+            | TimelockedCall::SetTreasury { .. }
+            | TimelockedCall::SetFeeMode { .. }
+            | TimelockedCall::SetKeeperRewardConfig { .. }
+            | TimelockedCall::SetMinStake { .. }
+            | TimelockedCall::SetStakeCaps { .. }
+            | TimelockedCall::SetCapExempt { .. }
+            | TimelockedCall::SetDelegationThreshold { .. }
+            | TimelockedCall::SetUnbondingDelay { .. }
+            | TimelockedCall::SetLiquidityBufferConfig { .. } => {
+                self.env().revert(LendingError::UnsupportedTimelockCall)
+            }
+        }
+    }
+
+    fn require_not_paused(&self, action: LendingAction) {
+        if self.is_action_paused(action) {
+            self.env().revert(LendingError::ActionPaused);
+        }
+    }
+
+    /// Revert unless the caller has `role` or is the admin
+    fn require_role(&self, role: Role) {
+        let caller = self.env().caller();
+        if self.admin.get() == Some(caller) || self.roles.has_role(role, caller) {
+            return;
+        }
+        self.env().revert(LendingError::MissingRole);
+    }
 }
@@ -13,21 +13,38 @@ extern crate alloc;
 pub mod auction_interface;
 pub mod errors;
 pub mod events;
+pub mod fee_splitter;
+pub mod interest_rate;
 pub mod lending_pool;
+pub mod roles;
+pub mod share_math;
+pub mod tcspr_token;
 pub mod thcspr_token;
 pub mod thaw_core;
+pub mod timelock;
 
 // Re-export main types for external use
 pub use errors::*;
 pub use events::*;
-pub use lending_pool::LendingPool;
+pub use fee_splitter::FeeRecipient;
+pub use interest_rate::InterestRateModel;
+pub use lending_pool::{
+    LendingAction, LendingPauseState, LendingPool, LeveragePreview, PendingUnwind,
+};
+pub use tcspr_token::TCsprToken;
 pub use thcspr_token::ThCsprToken;
-pub use thaw_core::{ThawCore, WithdrawalRequest};
+pub use timelock::{QueuedOperation, TimelockedCall};
+pub use thaw_core::{
+    CompoundInfo, FeeMode, InstantUnstakeOutcome, KeeperRewardConfig, LiquidityBufferConfig,
+    ThawCore, ValidatorAllocation, ValidatorMigration, WithdrawalRequest,
+};
 
 // Re-export generated types only when not building for wasm32 target
 #[cfg(not(target_arch = "wasm32"))]
 pub use lending_pool::{LendingPoolHostRef, LendingPoolInitArgs};
 #[cfg(not(target_arch = "wasm32"))]
+pub use tcspr_token::{TCsprTokenHostRef, TCsprTokenInitArgs};
+#[cfg(not(target_arch = "wasm32"))]
 pub use thcspr_token::{ThCsprTokenHostRef, ThCsprTokenInitArgs};
 #[cfg(not(target_arch = "wasm32"))]
 pub use thaw_core::{ThawCoreHostRef, ThawCoreInitArgs};
@@ -0,0 +1,73 @@
+//! Roles - Role-based access control shared by ThawCore and LendingPool
+//!
+//! Built on `odra_modules::access::AccessControl`. The host contract's admin
+//! grants and revokes roles and implicitly holds every role, so operations
+//! can be delegated (e.g. pause rights to an ops team) without handing over
+//! the rest of the admin powers. Grants emit `RoleGranted` / `RoleRevoked`.
+
+use odra::prelude::*;
+use odra_modules::access::AccessControl;
+
+pub use odra_modules::access::Role;
+
+/// Can pause and unpause the contract (guardian)
+pub const PAUSER_ROLE: Role = role_id(b"PAUSER");
+/// Can change protocol fees, fee recipients and the treasury
+pub const FEE_MANAGER_ROLE: Role = role_id(b"FEE_MANAGER");
+/// Can add, remove, reweight and migrate validators
+pub const VALIDATOR_MANAGER_ROLE: Role = role_id(b"VALIDATOR_MANAGER");
+/// Can change risk parameters (caps, buffer, unbonding, lending config)
+pub const RISK_MANAGER_ROLE: Role = role_id(b"RISK_MANAGER");
+/// Can run keeper operations that are restricted to keepers
+pub const KEEPER_ROLE: Role = role_id(b"KEEPER");
+
+/// All roles a host contract accepts
+pub const ROLES: [Role; 5] = [
+    PAUSER_ROLE,
+    FEE_MANAGER_ROLE,
+    VALIDATOR_MANAGER_ROLE,
+    RISK_MANAGER_ROLE,
+    KEEPER_ROLE,
+];
+
+/// Role identifier: the role name, zero padded to 32 bytes
+const fn role_id(name: &[u8]) -> Role {
+    let mut id = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        id[i] = name[i];
+        i += 1;
+    }
+    id
+}
+
+/// Check whether `role` is one of the protocol roles
+pub fn is_known_role(role: &Role) -> bool {
+    ROLES.contains(role)
+}
+
+/// Role membership for a host contract
+#[odra::module]
+pub struct Roles {
+    access: SubModule<AccessControl>,
+}
+
+#[odra::module]
+impl Roles {
+    /// Check whether `account` has been granted `role`
+    pub fn has_role(&self, role: Role, account: Address) -> bool {
+        self.access.has_role(&role, &account)
+    }
+}
+
+impl Roles {
+    /// Grant `role` to `account` (the host checks permissions first)
+    pub fn grant(&mut self, role: Role, account: Address) {
+        self.access.unchecked_grant_role(&role, &account);
+    }
+
+    /// Revoke `role` from `account` (the host checks permissions first)
+    pub fn revoke(&mut self, role: Role, account: Address) {
+        self.access.unchecked_revoke_role(&role, &account);
+    }
+}
@@ -0,0 +1,55 @@
+//! Share math - Conversions between CSPR and thCSPR
+//!
+//! Conversions add a virtual offset to both the pooled CSPR and the thCSPR
+//! supply. The offset keeps a 1:1 rate for an empty or balanced pool, but an
+//! attacker can no longer inflate the price of a share with a donation so that
+//! later deposits round down to zero: the donation is shared with the virtual
+//! shares and mostly lost.
+//!
+//! Rounding always favours the pool: shares minted and CSPR paid out round
+//! down, shares burned for a CSPR amount round up.
+
+use odra::casper_types::U512;
+
+/// Virtual CSPR and thCSPR added to both sides of every conversion (motes)
+pub const VIRTUAL_OFFSET: u64 = 1_000_000; // 0.001 CSPR
+
+/// Rounding direction of a conversion
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Convert a CSPR amount to thCSPR shares
+///
+/// # Arguments
+/// * `assets` - CSPR amount (motes)
+/// * `total_assets` - Total pooled CSPR
+/// * `total_shares` - Total thCSPR supply
+pub fn to_shares(assets: U512, total_assets: U512, total_shares: U512, rounding: Rounding) -> U512 {
+    let offset = U512::from(VIRTUAL_OFFSET);
+    mul_div(assets, total_shares + offset, total_assets + offset, rounding)
+}
+
+/// Convert thCSPR shares to a CSPR amount
+///
+/// # Arguments
+/// * `shares` - thCSPR amount
+/// * `total_assets` - Total pooled CSPR
+/// * `total_shares` - Total thCSPR supply
+pub fn to_assets(shares: U512, total_assets: U512, total_shares: U512, rounding: Rounding) -> U512 {
+    let offset = U512::from(VIRTUAL_OFFSET);
+    mul_div(shares, total_assets + offset, total_shares + offset, rounding)
+}
+
+/// Compute `value * numerator / denominator` with the given rounding
+pub fn mul_div(value: U512, numerator: U512, denominator: U512, rounding: Rounding) -> U512 {
+    let product = value * numerator;
+    let result = product / denominator;
+    if rounding == Rounding::Up && product % denominator != U512::zero() {
+        result + U512::one()
+    } else {
+        result
+    }
+}
@@ -9,10 +9,31 @@ use odra::casper_types::{PublicKey, U512};
 use odra::ContractRef;
 
 use crate::errors::Error;
-use crate::events::{AdminTransferred, Claimed, Compounded, FeeUpdated, Paused, Staked, Unpaused, Unstaked};
+use crate::fee_splitter::{is_valid_split, FeeRecipient, FeeSplitter};
+use crate::roles::{
+    is_known_role, Role, Roles, FEE_MANAGER_ROLE, KEEPER_ROLE, PAUSER_ROLE, RISK_MANAGER_ROLE,
+    VALIDATOR_MANAGER_ROLE,
+};
+use crate::share_math::{self, Rounding};
+use crate::events::{
+    AdminTransferCancelled, AdminTransferProposed, AdminTransferred, AllowlistModeUpdated,
+    AllowlistUpdated, CapExemptionUpdated, Claimed, Compounded, DelegationThresholdUpdated, DelegationsFlushed,
+    FeeModeUpdated, FeeRecipientsUpdated, FeeUpdated, InstantUnstaked, KeeperRewardConfigUpdated,
+    LiquidityBufferConfigUpdated, OperationCancelled, OperationExecuted, OperationQueued, Paused,
+    Slashed, StakeCapsUpdated, Staked, TimelockDelayUpdated, Unpaused, Unstaked, ValidatorAdded,
+    ValidatorEvicted, ValidatorMigrationCompleted, ValidatorMigrationStarted, ValidatorRedelegated,
+    UnbondingDelayUpdated, ValidatorRemoved, ValidatorWeightUpdated, WithdrawalApproved,
+    WithdrawalTransferred,
+};
 use crate::thcspr_token::ThCsprTokenContractRef;
+use crate::timelock::{
+    QueuedOperation, Timelock, TimelockedCall, DEFAULT_TIMELOCK_DELAY_MS, MAX_TIMELOCK_DELAY_MS,
+};
 
 /// Withdrawal request structure
+///
+/// Requests are transferable tickets: `user` is the current owner and the
+/// only address able to claim.
 #[odra::odra_type]
 pub struct WithdrawalRequest {
     pub id: u64,
@@ -22,6 +43,82 @@ pub struct WithdrawalRequest {
     pub request_timestamp: u64,
     pub claimable_timestamp: u64,
     pub claimed: bool,
+    /// Withdrawal loss index at request time, used to apply later losses
+    pub loss_index: U512,
+}
+
+/// Current allocation of a validator in the delegation set
+#[odra::odra_type]
+pub struct ValidatorAllocation {
+    pub validator: PublicKey,
+    pub weight: u64,
+    /// Target share of total delegation (basis points)
+    pub target_bps: u64,
+    /// CSPR currently delegated to the validator (motes)
+    pub delegated: U512,
+}
+
+/// Liquidity buffer and instant unstake fee settings (basis points)
+#[odra::odra_type]
+pub struct LiquidityBufferConfig {
+    /// Target buffer size as a share of total pooled CSPR
+    pub target_bps: u64,
+    /// Share of each new stake kept in the buffer while below target
+    pub stake_share_bps: u64,
+    /// Instant unstake fee when the buffer stays at or above target
+    pub min_fee_bps: u64,
+    /// Instant unstake fee when the buffer would be fully drained
+    pub max_fee_bps: u64,
+}
+
+/// What `instant_unstake` did with a request
+#[odra::odra_type]
+pub struct InstantUnstakeOutcome {
+    /// CSPR paid out of the buffer (zero if the request was queued)
+    pub cspr_amount: U512,
+    /// Withdrawal request created when the buffer could not cover the amount
+    pub withdrawal_id: Option<u64>,
+}
+
+/// How the protocol fee on rewards is paid to the treasury
+#[odra::odra_type]
+pub enum FeeMode {
+    /// Transfer the fee in CSPR out of the pool's liquid CSPR
+    Cspr,
+    /// Mint thCSPR worth the fee, keeping the whole reward delegated
+    Shares,
+}
+
+/// Compound keeper reward settings
+#[odra::odra_type]
+pub struct KeeperRewardConfig {
+    /// Keeper share of harvested rewards (basis points)
+    pub reward_bps: u64,
+    /// Upper bound on the keeper reward per compound (motes)
+    pub max_reward: U512,
+    /// Minimum time between two compounds (ms)
+    pub min_interval_ms: u64,
+}
+
+/// Snapshot used by keepers to decide whether to call `compound`
+#[odra::odra_type]
+pub struct CompoundInfo {
+    pub pending_rewards: U512,
+    /// Block time of the last compound, zero if never compounded
+    pub last_compound_timestamp: u64,
+    /// Earliest block time at which `compound` can be called
+    pub next_compound_timestamp: u64,
+    pub expected_keeper_reward: U512,
+}
+
+/// In-flight move of delegated CSPR from one validator to another
+#[odra::odra_type]
+pub struct ValidatorMigration {
+    pub from: PublicKey,
+    pub to: PublicKey,
+    /// Total CSPR undelegated from `from` (motes)
+    pub amount: U512,
+    pub started_timestamp: u64,
 }
 
 /// ThawCore - Main liquid staking contract
@@ -35,19 +132,62 @@ pub struct ThawCore {
     total_thcspr_supply: Var<U512>,
 
     // Staking config
-    validator_public_key: Var<PublicKey>,
+    validators: Var<Vec<PublicKey>>,
+    validator_weights: Mapping<PublicKey, u64>,
+    migration: Var<Option<ValidatorMigration>>,
+    pending_redelegation: Var<U512>,
+    pending_delegation: Var<U512>,
+    delegation_threshold: Var<U512>,
 
     // Fees (basis points, 10000 = 100%)
     protocol_fee_bps: Var<u64>,
+    fee_mode: Var<FeeMode>,
     treasury: Var<Address>,
+    fee_splitter: SubModule<FeeSplitter>,
+
+    // Compound keeper incentives
+    keeper_reward_bps: Var<u64>,
+    keeper_max_reward: Var<U512>,
+    compound_min_interval_ms: Var<u64>,
+    last_compound_timestamp: Var<u64>,
+
+    // Liquidity buffer for instant unstakes (undelegated, part of the pool)
+    liquidity_buffer: Var<U512>,
+    buffer_target_bps: Var<u64>,
+    buffer_stake_share_bps: Var<u64>,
+    instant_fee_min_bps: Var<u64>,
+    instant_fee_max_bps: Var<u64>,
 
     // Withdrawals
     withdrawal_counter: Var<u64>,
     withdrawals: Mapping<u64, WithdrawalRequest>,
     user_withdrawals: Mapping<Address, Vec<u64>>,
+    withdrawal_approvals: Mapping<u64, Option<Address>>,
+    total_pending_withdrawals: Var<U512>,
+    unbonding_delay_ms: Var<u64>,
+
+    // Referrals (total CSPR staked per referrer)
+    referral_totals: Mapping<Address, U512>,
+
+    // Deposit limits (zero cap means unlimited)
+    stake_cap: Var<U512>,
+    per_address_cap: Var<U512>,
+    allowlist_enabled: Var<bool>,
+    allowlist: Mapping<Address, bool>,
+    cap_exempt: Mapping<Address, bool>,
+
+    // Loss socialization
+    withdrawal_loss_index: Var<U512>,
+    pool_unbonding: Var<U512>,
+    pool_unbonding_settles_at: Var<u64>,
+    known_delegations: Mapping<PublicKey, U512>,
 
     // Admin
     admin: Var<Address>,
+    pending_admin: Var<Option<Address>>,
+    roles: SubModule<Roles>,
+    keeper_only_compound: Var<bool>,
+    timelock: SubModule<Timelock>,
     is_paused: Var<bool>,
 
     // Constants
@@ -57,9 +197,18 @@ pub struct ThawCore {
 // Constants
 const EXCHANGE_RATE_PRECISION: u128 = 1_000_000_000_000_000_000; // 1e18
 const DEFAULT_MIN_STAKE: u64 = 10_000_000_000; // 10 CSPR in motes
+const MAX_STAKE_AMOUNT: u64 = u64::MAX; // motes, above the total CSPR supply
 const DEFAULT_FEE_BPS: u64 = 1000; // 10%
 const MAX_FEE_BPS: u64 = 3000; // 30%
-const UNBONDING_PERIOD_MS: u64 = 14 * 60 * 60 * 1000; // 14 hours
+const DEFAULT_UNBONDING_DELAY_MS: u64 = 14 * 60 * 60 * 1000; // 14 hours (7 eras on mainnet)
+const MAX_UNBONDING_DELAY_MS: u64 = 30 * 24 * 60 * 60 * 1000; // 30 days
+const DEFAULT_VALIDATOR_WEIGHT: u64 = 10000;
+const MAX_VALIDATORS: usize = 10;
+const BPS_PRECISION: u64 = 10000;
+const DEFAULT_INSTANT_FEE_MIN_BPS: u64 = 10; // 0.1%
+const DEFAULT_INSTANT_FEE_MAX_BPS: u64 = 300; // 3%
+const MAX_INSTANT_FEE_BPS: u64 = 1000; // 10%
+const MAX_KEEPER_REWARD_BPS: u64 = 500; // 5%
 
 #[odra::module]
 impl ThawCore {
@@ -67,7 +216,7 @@ impl ThawCore {
     ///
     /// # Arguments
     /// * `thcspr_token` - Address of the thCSPR token contract
-    /// * `validator` - Public key of the validator to delegate to
+    /// * `validator` - Public key of the initial validator in the delegation set
     /// * `treasury` - Address where protocol fees are sent
     /// * `admin` - Admin address for contract management
     pub fn init(
@@ -78,15 +227,21 @@ impl ThawCore {
         admin: Address,
     ) {
         self.thcspr_token.set(thcspr_token);
-        self.validator_public_key.set(validator);
+        self.validator_weights.set(&validator, DEFAULT_VALIDATOR_WEIGHT);
+        self.validators.set(vec![validator]);
         self.treasury.set(treasury);
         self.admin.set(admin);
         self.protocol_fee_bps.set(DEFAULT_FEE_BPS);
+        self.instant_fee_min_bps.set(DEFAULT_INSTANT_FEE_MIN_BPS);
+        self.instant_fee_max_bps.set(DEFAULT_INSTANT_FEE_MAX_BPS);
         self.min_stake.set(U512::from(DEFAULT_MIN_STAKE));
         self.total_pooled_cspr.set(U512::zero());
         self.total_thcspr_supply.set(U512::zero());
         self.is_paused.set(false);
         self.withdrawal_counter.set(0);
+        self.unbonding_delay_ms.set(DEFAULT_UNBONDING_DELAY_MS);
+        self.withdrawal_loss_index.set(U512::from(EXCHANGE_RATE_PRECISION));
+        self.timelock.set_delay(DEFAULT_TIMELOCK_DELAY_MS);
     }
 
     // ============ CORE FUNCTIONS ============
@@ -94,109 +249,107 @@ impl ThawCore {
     /// Stake CSPR and receive thCSPR
     #[odra(payable)]
     pub fn stake(&mut self) -> U512 {
-        self.require_not_paused();
-
         let caller = self.env().caller();
-        let cspr_amount = self.env().attached_value();
+        self.stake_internal(caller, None)
+    }
 
-        // Validate minimum
-        let min = self.min_stake.get_or_default();
-        if cspr_amount < min {
-            self.env().revert(Error::BelowMinimumStake);
+    /// Stake CSPR on behalf of `recipient`, who receives the thCSPR
+    ///
+    /// Lets custodians and partner contracts deposit for their users. The
+    /// stake is added to the referrer's total when one is given.
+    ///
+    /// # Arguments
+    /// * `recipient` - Address receiving the minted thCSPR
+    /// * `referral` - Optional referrer credited with the stake
+    #[odra(payable)]
+    pub fn stake_for(&mut self, recipient: Address, referral: Option<Address>) -> U512 {
+        if referral == Some(recipient) {
+            self.env().revert(Error::InvalidReferrer);
         }
+        self.stake_internal(recipient, referral)
+    }
 
-        // Calculate thCSPR to mint
-        let thcspr_amount = self.cspr_to_thcspr(cspr_amount);
-
-        // Update state BEFORE external calls (CEI pattern)
-        let new_total_pooled = self.total_pooled_cspr.get_or_default() + cspr_amount;
-        let new_total_supply = self.total_thcspr_supply.get_or_default() + thcspr_amount;
-        self.total_pooled_cspr.set(new_total_pooled);
-        self.total_thcspr_supply.set(new_total_supply);
-
-        // Mint thCSPR to user via cross-contract call
-        self.mint_thcspr(caller, thcspr_amount);
-
-        // Delegate CSPR to validator via System Auction
-        self.delegate_to_validator(cspr_amount);
+    /// Request unstake - burns thCSPR, queues withdrawal
+    pub fn unstake(&mut self, thcspr_amount: U512) -> u64 {
+        self.require_not_paused();
 
-        // Emit event
-        self.env().emit_event(Staked {
-            user: caller,
-            cspr_amount,
-            thcspr_minted: thcspr_amount,
-            exchange_rate: self.get_exchange_rate(),
-        });
+        let caller = self.env().caller();
+        self.require_unstake_balance(caller, thcspr_amount);
 
-        thcspr_amount
+        self.queue_withdrawal(caller, thcspr_amount)
     }
 
-    /// Request unstake - burns thCSPR, queues withdrawal
-    pub fn unstake(&mut self, thcspr_amount: U512) -> u64 {
+    /// Unstake immediately out of the liquidity buffer for a dynamic fee
+    ///
+    /// The fee stays in the pool, so remaining stakers earn it. When the
+    /// buffer cannot cover the full amount, the request falls back to the
+    /// regular withdrawal queue (see `unstake`).
+    ///
+    /// # Arguments
+    /// * `thcspr_amount` - Amount of thCSPR to burn
+    /// * `max_fee_bps` - Highest fee the caller accepts (basis points)
+    ///
+    /// # Returns
+    /// CSPR paid out immediately, or the id of the queued withdrawal request
+    pub fn instant_unstake(
+        &mut self,
+        thcspr_amount: U512,
+        max_fee_bps: u64,
+    ) -> InstantUnstakeOutcome {
         self.require_not_paused();
 
         let caller = self.env().caller();
+        self.require_unstake_balance(caller, thcspr_amount);
 
-        // Validate amount
-        if thcspr_amount == U512::zero() {
-            self.env().revert(Error::AmountMustBePositive);
+        let cspr_amount = self.thcspr_to_cspr(thcspr_amount);
+        let buffer = self.liquidity_buffer.get_or_default();
+
+        // Not enough liquidity - go through the unbonding queue instead
+        if buffer == U512::zero() || cspr_amount > buffer {
+            let withdrawal_id = self.queue_withdrawal(caller, thcspr_amount);
+            return InstantUnstakeOutcome {
+                cspr_amount: U512::zero(),
+                withdrawal_id: Some(withdrawal_id),
+            };
         }
 
-        // Validate user has sufficient thCSPR balance
-        let balance = self.get_thcspr_balance(caller);
-        if balance < thcspr_amount {
-            self.env().revert(Error::InsufficientBalance);
+        let fee_bps = self.get_instant_unstake_fee_bps(cspr_amount);
+        if fee_bps > max_fee_bps {
+            self.env().revert(Error::InstantFeeTooHigh);
         }
-
-        // Calculate CSPR to return
-        let cspr_amount = self.thcspr_to_cspr(thcspr_amount);
+        let fee = share_math::mul_div(
+            cspr_amount,
+            U512::from(fee_bps),
+            U512::from(BPS_PRECISION),
+            Rounding::Up,
+        );
+        let payout = cspr_amount - fee;
 
         // Update state BEFORE external calls (CEI pattern)
-        let new_total_pooled = self.total_pooled_cspr.get_or_default() - cspr_amount;
+        let new_total_pooled = self.total_pooled_cspr.get_or_default() - payout;
         let new_total_supply = self.total_thcspr_supply.get_or_default() - thcspr_amount;
         self.total_pooled_cspr.set(new_total_pooled);
         self.total_thcspr_supply.set(new_total_supply);
+        self.liquidity_buffer.set(buffer - payout);
 
         // Burn thCSPR via cross-contract call
         self.burn_thcspr(caller, thcspr_amount);
 
-        // Undelegate from validator via System Auction
-        self.undelegate_from_validator(cspr_amount);
-
-        // Create withdrawal request
-        let withdrawal_id = self.withdrawal_counter.get_or_default();
-        self.withdrawal_counter.set(withdrawal_id + 1);
-
-        let now = self.env().get_block_time();
-        let claimable = now + UNBONDING_PERIOD_MS;
-
-        let request = WithdrawalRequest {
-            id: withdrawal_id,
-            user: caller,
-            cspr_amount,
-            thcspr_burned: thcspr_amount,
-            request_timestamp: now,
-            claimable_timestamp: claimable,
-            claimed: false,
-        };
-
-        self.withdrawals.set(&withdrawal_id, request);
-
-        // Track user's withdrawals
-        let mut user_ids = self.user_withdrawals.get(&caller).unwrap_or_default();
-        user_ids.push(withdrawal_id);
-        self.user_withdrawals.set(&caller, user_ids);
+        // Pay out of the buffer
+        self.env().transfer_tokens(&caller, &payout);
 
-        // Emit event
-        self.env().emit_event(Unstaked {
+        self.env().emit_event(InstantUnstaked {
             user: caller,
             thcspr_burned: thcspr_amount,
-            cspr_amount,
-            withdrawal_id,
-            claimable_timestamp: claimable,
+            cspr_amount: payout,
+            fee,
+            fee_bps,
         });
 
-        withdrawal_id
+        InstantUnstakeOutcome {
+            cspr_amount: payout,
+            withdrawal_id: None,
+        }
     }
 
     /// Claim CSPR after unbonding period
@@ -220,20 +373,31 @@ impl ThawCore {
             self.env().revert(Error::StillUnbonding);
         }
 
+        // Losses socialized since the request reduce the payout
+        let cspr_amount = self.withdrawal_payout(&request);
+
+        // The unbonded CSPR must actually be back in the contract purse
+        if cspr_amount > self.get_claimable_liquidity() {
+            self.env().revert(Error::FundsNotYetUnbonded);
+        }
+
         request.claimed = true;
-        self.withdrawals.set(&withdrawal_id, request.clone());
+        self.withdrawals.set(&withdrawal_id, request);
+        let pending_withdrawals = self.total_pending_withdrawals.get_or_default();
+        self.total_pending_withdrawals
+            .set(pending_withdrawals.saturating_sub(cspr_amount));
 
         // Transfer CSPR to user
-        self.env().transfer_tokens(&caller, &request.cspr_amount);
+        self.env().transfer_tokens(&caller, &cspr_amount);
 
         // Emit event
         self.env().emit_event(Claimed {
             user: caller,
             withdrawal_id,
-            cspr_amount: request.cspr_amount,
+            cspr_amount,
         });
 
-        request.cspr_amount
+        cspr_amount
     }
 
     /// Harvest and compound staking rewards
@@ -241,10 +405,29 @@ impl ThawCore {
     /// This function:
     /// 1. Gets pending rewards from the System Auction
     /// 2. Withdraws the rewards
-    /// 3. Deducts protocol fee and sends to treasury
-    /// 4. Adds remaining rewards to the pool (increases exchange rate)
-    /// 5. Re-delegates rewards to validator for compounding
+    /// 3. Deducts protocol fee and sends it to the fee recipients
+    /// 4. Pays the keeper reward to the caller
+    /// 5. Adds remaining rewards to the pool (increases exchange rate)
+    ///
+    /// Rewards already compound inside the delegations, so CSPR payouts are
+    /// made out of the pool's liquid CSPR (batched stakes, then the liquidity
+    /// buffer). In `FeeMode::Shares` the fee stays in the pool and the
+    /// treasury is minted thCSPR worth the fee instead. A fee or keeper
+    /// reward the liquid CSPR cannot cover is paid in thCSPR the same way,
+    /// so compounding never stalls on liquidity. Reverts with
+    /// `CompoundTooSoon` if called before the minimum interval since the last
+    /// compound has elapsed. Restricted to keepers when keeper-only compound
+    /// is enabled.
     pub fn compound(&mut self) -> U512 {
+        if self.keeper_only_compound.get_or_default() {
+            self.require_role(KEEPER_ROLE);
+        }
+
+        let now = self.env().get_block_time();
+        if now < self.get_next_compound_timestamp() {
+            self.env().revert(Error::CompoundTooSoon);
+        }
+
         // Get pending rewards from System Auction
         let rewards = self.get_pending_rewards();
 
@@ -254,25 +437,56 @@ impl ThawCore {
 
         // Withdraw rewards from System Auction
         self.withdraw_rewards();
+        self.last_compound_timestamp.set(now);
 
-        // Calculate protocol fee
+        // Calculate protocol fee and keeper reward
         let fee_bps = self.protocol_fee_bps.get_or_default();
         let protocol_fee = rewards * U512::from(fee_bps) / U512::from(10000u64);
-        let rewards_to_pool = rewards - protocol_fee;
+        let keeper_reward = self.keeper_reward_for(rewards);
+        let rewards_to_pool = rewards - protocol_fee - keeper_reward;
+
+        // Fall back to thCSPR for whatever the liquid CSPR cannot cover
+        let mut liquidity =
+            self.pending_delegation.get_or_default() + self.liquidity_buffer.get_or_default();
+        let mut fee_mode = self.get_fee_mode();
+        if fee_mode == FeeMode::Cspr && protocol_fee > liquidity {
+            fee_mode = FeeMode::Shares;
+        }
+        if fee_mode == FeeMode::Cspr {
+            liquidity -= protocol_fee;
+        }
+        let keeper = self.env().caller();
+        let keeper_paid_in_cspr = keeper_reward <= liquidity;
 
-        // Send fee to treasury
-        if protocol_fee > U512::zero() {
-            let treasury = self.treasury.get().unwrap_or_revert_with(&self.env(), Error::TreasuryNotSet);
-            self.env().transfer_tokens(&treasury, &protocol_fee);
+        let fees = if protocol_fee > U512::zero() {
+            self.split_protocol_fee(protocol_fee)
+        } else {
+            Vec::new()
+        };
+        let mut share_payouts = Vec::new();
+        if fee_mode == FeeMode::Shares {
+            share_payouts.extend(fees.iter().cloned());
+        }
+        if !keeper_paid_in_cspr {
+            share_payouts.push((keeper, keeper_reward));
         }
 
         // Add rewards to pool (increases exchange rate)
-        let new_total = self.total_pooled_cspr.get_or_default() + rewards_to_pool;
-        self.total_pooled_cspr.set(new_total);
+        self.mint_reward_shares(rewards_to_pool, share_payouts);
+
+        // Send fee to the fee recipients, accrued totals are kept in CSPR
+        for (recipient, fee) in fees {
+            if fee > U512::zero() {
+                if fee_mode == FeeMode::Cspr {
+                    self.pay_from_liquidity(recipient, fee);
+                }
+                self.fee_splitter.accrue(recipient, fee);
+            }
+        }
 
-        // Re-delegate rewards to validator for compounding
-        if rewards_to_pool > U512::zero() {
-            self.delegate_to_validator(rewards_to_pool);
+        // Pay the keeper
+        if keeper_paid_in_cspr && keeper_reward > U512::zero() {
+            self.pay_from_liquidity(keeper, keeper_reward);
         }
 
         // Emit event
@@ -281,129 +495,1210 @@ impl ThawCore {
             protocol_fee,
             rewards_to_pool,
             new_exchange_rate: self.get_exchange_rate(),
+            keeper,
+            keeper_reward,
+            fee_mode,
         });
 
         rewards_to_pool
     }
 
-    // ============ VIEW FUNCTIONS ============
+    /// Detect and socialize a shortfall in the pool
+    ///
+    /// Compares total pooled CSPR with what actually backs it: CSPR delegated
+    /// across the validator set, CSPR unbonding back to the pool and the
+    /// liquidity buffer. A shortfall (slashing, CSPR that never came back
+    /// from unbonding) is split between thCSPR holders and pending withdrawal
+    /// requests in proportion to their claims, so the exchange rate falls and
+    /// every unclaimed request pays out proportionally less. Anyone can call
+    /// it.
+    ///
+    /// The delegations of an evicted validator are unbonded back to the
+    /// contract by the auction, so they are tracked as pool CSPR unbonding
+    /// rather than lost. CSPR that has come back to the contract purse without
+    /// being owed to a withdrawal is moved into the liquidity buffer. Pool CSPR
+    /// still missing once the unbonding delay has passed is written off.
+    ///
+    /// # Returns
+    /// Loss socialized by this call
+    pub fn sync(&mut self) -> U512 {
+        self.book_evicted_delegations();
+        self.sweep_returned_cspr();
 
-    /// Get current exchange rate (18 decimal precision)
-    pub fn get_exchange_rate(&self) -> U512 {
+        // Whatever has not come back by now is not coming back
+        let now = self.env().get_block_time();
+        if now >= self.pool_unbonding_settles_at.get_or_default() {
+            self.pool_unbonding.set(U512::zero());
+        }
+
+        let backing = self.get_pool_backing();
         let total_pooled = self.total_pooled_cspr.get_or_default();
-        let total_supply = self.total_thcspr_supply.get_or_default();
+        if backing >= total_pooled {
+            return U512::zero();
+        }
 
-        if total_supply == U512::zero() {
-            return U512::from(EXCHANGE_RATE_PRECISION);
+        let loss = total_pooled - backing;
+        let pending_withdrawals = self.total_pending_withdrawals.get_or_default();
+        let withdrawals_loss = loss * pending_withdrawals / (total_pooled + pending_withdrawals);
+        let holders_loss = loss - withdrawals_loss;
+        let old_exchange_rate = self.get_exchange_rate();
+
+        self.total_pooled_cspr.set(total_pooled - holders_loss);
+
+        // The requests now pay out less; what they no longer claim stays in
+        // the pool once their unbonding CSPR comes back
+        if withdrawals_loss > U512::zero() {
+            let remaining = pending_withdrawals - withdrawals_loss;
+            let index = self.get_withdrawal_loss_index();
+            self.withdrawal_loss_index
+                .set(index * remaining / pending_withdrawals);
+            self.total_pending_withdrawals.set(remaining);
+            self.add_pool_unbonding(withdrawals_loss);
         }
 
-        (total_pooled * U512::from(EXCHANGE_RATE_PRECISION)) / total_supply
-    }
+        self.env().emit_event(Slashed {
+            loss,
+            holders_loss,
+            withdrawals_loss,
+            old_exchange_rate,
+            new_exchange_rate: self.get_exchange_rate(),
+        });
 
-    pub fn get_total_pooled(&self) -> U512 {
-        self.total_pooled_cspr.get_or_default()
+        loss
     }
 
-    pub fn get_total_supply(&self) -> U512 {
-        self.total_thcspr_supply.get_or_default()
+    /// Delegate batched stakes once they reach the delegation threshold
+    ///
+    /// Stakes are normally flushed by the stake that crosses the threshold;
+    /// this lets anyone flush after the threshold was lowered. Reverts while
+    /// the pending amount is below the threshold.
+    ///
+    /// # Returns
+    /// Amount delegated
+    pub fn flush_delegations(&mut self) -> U512 {
+        let pending = self.pending_delegation.get_or_default();
+        if pending == U512::zero() || pending < self.delegation_threshold.get_or_default() {
+            self.env().revert(Error::BelowDelegationThreshold);
+        }
+        self.flush_pending_delegation()
     }
 
-    pub fn get_min_stake(&self) -> U512 {
-        self.min_stake.get_or_default()
-    }
+    // ============ WITHDRAWAL TICKETS ============
 
-    pub fn get_protocol_fee_bps(&self) -> u64 {
-        self.protocol_fee_bps.get_or_default()
-    }
+    /// Transfer an unclaimed withdrawal request to another address
+    ///
+    /// Callable by the current owner or the address approved for this
+    /// withdrawal. The new owner is the only one able to claim it, and any
+    /// existing approval is cleared.
+    pub fn transfer_withdrawal(&mut self, withdrawal_id: u64, to: Address) {
+        let caller = self.env().caller();
 
-    pub fn is_paused(&self) -> bool {
-        self.is_paused.get_or_default()
-    }
+        let mut request = self
+            .withdrawals
+            .get(&withdrawal_id)
+            .unwrap_or_revert_with(&self.env(), Error::WithdrawalNotFound);
 
-    pub fn get_withdrawal(&self, withdrawal_id: u64) -> Option<WithdrawalRequest> {
-        self.withdrawals.get(&withdrawal_id)
-    }
+        if request.claimed {
+            self.env().revert(Error::AlreadyClaimed);
+        }
 
-    pub fn get_user_withdrawals(&self, user: Address) -> Vec<WithdrawalRequest> {
-        let ids = self.user_withdrawals.get(&user).unwrap_or_default();
-        ids.iter()
-            .filter_map(|id| self.withdrawals.get(id))
-            .collect()
-    }
+        let from = request.user;
+        let approved = self.withdrawal_approvals.get(&withdrawal_id).flatten();
+        if caller != from && approved != Some(caller) {
+            self.env().revert(Error::NotWithdrawalApproved);
+        }
 
-    /// Get the configured validator public key
-    pub fn get_validator(&self) -> Option<PublicKey> {
-        self.validator_public_key.get()
-    }
+        request.user = to;
+        self.withdrawals.set(&withdrawal_id, request);
+        self.withdrawal_approvals.set(&withdrawal_id, None);
 
-    /// Get the treasury address
-    pub fn get_treasury(&self) -> Option<Address> {
-        self.treasury.get()
+        // Move the id between the owners' withdrawal lists
+        let mut from_ids = self.user_withdrawals.get(&from).unwrap_or_default();
+        from_ids.retain(|id| *id != withdrawal_id);
+        self.user_withdrawals.set(&from, from_ids);
+
+        let mut to_ids = self.user_withdrawals.get(&to).unwrap_or_default();
+        to_ids.push(withdrawal_id);
+        self.user_withdrawals.set(&to, to_ids);
+
+        self.env().emit_event(WithdrawalTransferred {
+            withdrawal_id,
+            from,
+            to,
+        });
     }
 
-    /// Get the admin address
-    pub fn get_admin(&self) -> Option<Address> {
-        self.admin.get()
+    /// Approve `spender` to transfer a withdrawal request (owner only)
+    ///
+    /// Pass `None` to revoke the current approval.
+    pub fn approve_withdrawal(&mut self, withdrawal_id: u64, spender: Option<Address>) {
+        let caller = self.env().caller();
+
+        let request = self
+            .withdrawals
+            .get(&withdrawal_id)
+            .unwrap_or_revert_with(&self.env(), Error::WithdrawalNotFound);
+
+        if request.user != caller {
+            self.env().revert(Error::NotWithdrawalOwner);
+        }
+
+        if request.claimed {
+            self.env().revert(Error::AlreadyClaimed);
+        }
+
+        self.withdrawal_approvals.set(&withdrawal_id, spender);
+
+        self.env().emit_event(WithdrawalApproved {
+            withdrawal_id,
+            owner: caller,
+            spender,
+        });
     }
 
-    // ============ INTERNAL FUNCTIONS ============
+    // ============ VALIDATOR MIGRATION ============
 
-    fn cspr_to_thcspr(&self, cspr_amount: U512) -> U512 {
-        let total_pooled = self.total_pooled_cspr.get_or_default();
-        let total_supply = self.total_thcspr_supply.get_or_default();
+    /// Finish (or continue) an in-flight validator migration
+    ///
+    /// Re-delegates CSPR that has come back from unbonding to the migration
+    /// target. Anyone can call it. If only part of the undelegated amount is
+    /// back yet, that part is re-delegated and the call can be repeated in a
+    /// later era until nothing is pending.
+    ///
+    /// # Returns
+    /// Amount re-delegated by this call
+    pub fn complete_validator_migration(&mut self) -> U512 {
+        let migration = self
+            .migration
+            .get()
+            .flatten()
+            .unwrap_or_revert_with(&self.env(), Error::NoMigrationInProgress);
+
+        let pending = self.pending_redelegation.get_or_default();
+
+        // Never touch CSPR reserved for unclaimed withdrawals or the buffer
+        let reserved = self.total_pending_withdrawals.get_or_default()
+            + self.liquidity_buffer.get_or_default()
+            + self.pool_unbonding.get_or_default()
+            + self.pending_delegation.get_or_default();
+        let available = self.env().self_balance().saturating_sub(reserved);
+        let amount = pending.min(available);
+
+        if amount == U512::zero() && pending > U512::zero() {
+            self.env().revert(Error::NothingToRedelegate);
+        }
+
+        let remaining = pending - amount;
+        self.pending_redelegation.set(remaining);
+
+        if amount > U512::zero() {
+            self.env().delegate(migration.to.clone(), amount);
+            self.record_delegation(&migration.to);
+            self.env().emit_event(ValidatorRedelegated {
+                from: migration.from.clone(),
+                to: migration.to.clone(),
+                amount,
+                remaining,
+            });
+        }
 
-        if total_supply == U512::zero() || total_pooled == U512::zero() {
-            return cspr_amount;
+        if remaining == U512::zero() {
+            self.migration.set(None);
+            self.env().emit_event(ValidatorMigrationCompleted {
+                from: migration.from,
+                to: migration.to,
+                amount: migration.amount,
+            });
         }
 
-        (cspr_amount * total_supply) / total_pooled
+        amount
     }
 
-    fn thcspr_to_cspr(&self, thcspr_amount: U512) -> U512 {
+    // ============ VIEW FUNCTIONS ============
+
+    /// Get current exchange rate (18 decimal precision)
+    pub fn get_exchange_rate(&self) -> U512 {
         let total_pooled = self.total_pooled_cspr.get_or_default();
         let total_supply = self.total_thcspr_supply.get_or_default();
 
         if total_supply == U512::zero() {
-            return U512::zero();
+            return U512::from(EXCHANGE_RATE_PRECISION);
         }
 
-        (thcspr_amount * total_pooled) / total_supply
+        share_math::to_assets(
+            U512::from(EXCHANGE_RATE_PRECISION),
+            total_pooled,
+            total_supply,
+            Rounding::Down,
+        )
     }
 
-    fn require_not_paused(&self) {
-        if self.is_paused.get_or_default() {
-            self.env().revert(Error::ContractPaused);
-        }
+    /// Preview the thCSPR minted by staking `cspr_amount` right now
+    pub fn preview_stake(&self, cspr_amount: U512) -> U512 {
+        self.cspr_to_thcspr(cspr_amount, Rounding::Down)
     }
 
-    fn require_admin(&self) {
-        let admin = self.admin.get().unwrap_or_revert_with(&self.env(), Error::AdminNotSet);
-        if self.env().caller() != admin {
-            self.env().revert(Error::NotAdmin);
-        }
+    /// Preview the CSPR queued by unstaking `thcspr_amount` right now
+    pub fn preview_unstake(&self, thcspr_amount: U512) -> U512 {
+        self.thcspr_to_cspr(thcspr_amount)
     }
 
-    /// Mint thCSPR to user via cross-contract call
-    fn mint_thcspr(&self, to: Address, amount: U512) {
-        let token_address = self
-            .thcspr_token
-            .get()
-            .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
-        ThCsprTokenContractRef::new(self.env(), token_address).mint(to, amount);
+    /// Preview the thCSPR to unstake for at least `cspr_amount` right now (rounds up)
+    pub fn preview_withdraw(&self, cspr_amount: U512) -> U512 {
+        self.cspr_to_thcspr(cspr_amount, Rounding::Up)
     }
 
-    /// Burn thCSPR from user via cross-contract call
-    fn burn_thcspr(&self, from: Address, amount: U512) {
-        let token_address = self
-            .thcspr_token
-            .get()
-            .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
-        ThCsprTokenContractRef::new(self.env(), token_address).burn(from, amount);
+    /// Convert a CSPR amount to thCSPR at the current rate (rounds down)
+    pub fn convert_to_shares(&self, cspr_amount: U512) -> U512 {
+        self.cspr_to_thcspr(cspr_amount, Rounding::Down)
     }
 
-    /// Get thCSPR balance of a user via cross-contract call
-    fn get_thcspr_balance(&self, user: Address) -> U512 {
-        let token_address = self
+    /// Convert a thCSPR amount to CSPR at the current rate (rounds down)
+    pub fn convert_to_assets(&self, thcspr_amount: U512) -> U512 {
+        self.thcspr_to_cspr(thcspr_amount)
+    }
+
+    /// Get the most CSPR `user` can stake right now
+    ///
+    /// Zero while paused or when the room left under the caps is below the
+    /// minimum stake. Without caps this is bounded by the CSPR supply; the
+    /// contract cannot see account balances, so callers still clamp to the
+    /// staker's own balance.
+    pub fn max_stake(&self, user: Address) -> U512 {
+        if self.is_paused.get_or_default() {
+            return U512::zero();
+        }
+        let limit = self
+            .get_remaining_capacity(user)
+            .min(U512::from(MAX_STAKE_AMOUNT));
+        if limit < self.min_stake.get_or_default() {
+            return U512::zero();
+        }
+        limit
+    }
+
+    /// Get how much more CSPR can be staked for `user` under the caps
+    ///
+    /// Returns `U512::MAX` when no cap applies and zero when allowlist mode
+    /// is on and `user` is not allowlisted. The per-address cap does not
+    /// apply to cap-exempt addresses.
+    pub fn get_remaining_capacity(&self, user: Address) -> U512 {
+        if self.allowlist_enabled.get_or_default() && !self.is_allowlisted(user) {
+            return U512::zero();
+        }
+
+        let mut remaining = U512::MAX;
+        let global_cap = self.stake_cap.get_or_default();
+        if global_cap > U512::zero() {
+            remaining = global_cap.saturating_sub(self.total_pooled_cspr.get_or_default());
+        }
+        let address_cap = self.per_address_cap.get_or_default();
+        if address_cap > U512::zero() && !self.is_cap_exempt(user) {
+            let position = self.thcspr_to_cspr(self.get_thcspr_balance(user));
+            remaining = remaining.min(address_cap.saturating_sub(position));
+        }
+        remaining
+    }
+
+    /// Get the cap on total pooled CSPR (zero means no cap)
+    pub fn get_stake_cap(&self) -> U512 {
+        self.stake_cap.get_or_default()
+    }
+
+    /// Get the cap on a single address's position in CSPR (zero means no cap)
+    pub fn get_per_address_cap(&self) -> U512 {
+        self.per_address_cap.get_or_default()
+    }
+
+    /// Check whether only allowlisted addresses may receive new stakes
+    pub fn is_allowlist_enabled(&self) -> bool {
+        self.allowlist_enabled.get_or_default()
+    }
+
+    /// Check whether `account` is on the allowlist
+    pub fn is_allowlisted(&self, account: Address) -> bool {
+        self.allowlist.get(&account).unwrap_or_default()
+    }
+
+    /// Check whether `account` is exempt from the per-address cap
+    pub fn is_cap_exempt(&self, account: Address) -> bool {
+        self.cap_exempt.get(&account).unwrap_or_default()
+    }
+
+    /// Get the most thCSPR `user` can unstake right now (zero while paused)
+    pub fn max_unstake(&self, user: Address) -> U512 {
+        if self.is_paused.get_or_default() {
+            return U512::zero();
+        }
+        self.get_thcspr_balance(user)
+    }
+
+    pub fn get_total_pooled(&self) -> U512 {
+        self.total_pooled_cspr.get_or_default()
+    }
+
+    pub fn get_total_supply(&self) -> U512 {
+        self.total_thcspr_supply.get_or_default()
+    }
+
+    pub fn get_min_stake(&self) -> U512 {
+        self.min_stake.get_or_default()
+    }
+
+    pub fn get_protocol_fee_bps(&self) -> u64 {
+        self.protocol_fee_bps.get_or_default()
+    }
+
+    /// Get how the protocol fee is paid to the treasury
+    pub fn get_fee_mode(&self) -> FeeMode {
+        self.fee_mode.get().unwrap_or(FeeMode::Cspr)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.get_or_default()
+    }
+
+    pub fn get_withdrawal(&self, withdrawal_id: u64) -> Option<WithdrawalRequest> {
+        self.withdrawals.get(&withdrawal_id)
+    }
+
+    /// Get CSPR owed to unclaimed withdrawals that is still unbonding
+    pub fn get_total_unbonding(&self) -> U512 {
+        self.total_pending_withdrawals
+            .get_or_default()
+            .saturating_sub(self.get_claimable_liquidity())
+    }
+
+    /// Get CSPR in the contract purse available to pay withdrawal claims
+    ///
+    /// The purse balance minus the liquidity buffer and stakes waiting to be
+    /// delegated, capped at what unclaimed withdrawals are owed.
+    pub fn get_claimable_liquidity(&self) -> U512 {
+        let reserved =
+            self.liquidity_buffer.get_or_default() + self.pending_delegation.get_or_default();
+        self.env()
+            .self_balance()
+            .saturating_sub(reserved)
+            .min(self.total_pending_withdrawals.get_or_default())
+    }
+
+    /// Get the unbonding delay applied to new withdrawal requests (ms)
+    pub fn get_unbonding_delay(&self) -> u64 {
+        self.unbonding_delay_ms.get_or_default()
+    }
+
+    /// Get the CSPR a withdrawal request pays out if claimed now
+    ///
+    /// Lower than `cspr_amount` when a loss was socialized after the request.
+    pub fn get_withdrawal_payout(&self, withdrawal_id: u64) -> Option<U512> {
+        self.withdrawals
+            .get(&withdrawal_id)
+            .map(|request| self.withdrawal_payout(&request))
+    }
+
+    /// Get the cumulative loss index applied to pending withdrawals (18 decimals)
+    pub fn get_withdrawal_loss_index(&self) -> U512 {
+        self.withdrawal_loss_index
+            .get()
+            .unwrap_or(U512::from(EXCHANGE_RATE_PRECISION))
+    }
+
+    /// Get pool CSPR unbonding back to the contract that no withdrawal is owed
+    pub fn get_pool_unbonding(&self) -> U512 {
+        self.pool_unbonding.get_or_default()
+    }
+
+    /// Get the address approved to transfer a withdrawal request
+    pub fn get_withdrawal_approval(&self, withdrawal_id: u64) -> Option<Address> {
+        self.withdrawal_approvals.get(&withdrawal_id).flatten()
+    }
+
+    pub fn get_user_withdrawals(&self, user: Address) -> Vec<WithdrawalRequest> {
+        let ids = self.user_withdrawals.get(&user).unwrap_or_default();
+        ids.iter()
+            .filter_map(|id| self.withdrawals.get(id))
+            .collect()
+    }
+
+    /// Get the primary (first) validator of the delegation set
+    pub fn get_validator(&self) -> Option<PublicKey> {
+        self.validators.get_or_default().first().cloned()
+    }
+
+    /// Get all validators in the delegation set
+    pub fn get_validators(&self) -> Vec<PublicKey> {
+        self.validators.get_or_default()
+    }
+
+    /// Get the target weight and current delegation of a validator
+    pub fn get_validator_allocation(&self, validator: PublicKey) -> Option<ValidatorAllocation> {
+        if !self.validators.get_or_default().contains(&validator) {
+            return None;
+        }
+        let weight = self.validator_weights.get(&validator).unwrap_or_default();
+        let total_weight = self.get_total_validator_weight();
+        let target_bps = if total_weight == 0 {
+            0
+        } else {
+            weight * BPS_PRECISION / total_weight
+        };
+        Some(ValidatorAllocation {
+            delegated: self.env().delegated_amount(validator.clone()),
+            validator,
+            weight,
+            target_bps,
+        })
+    }
+
+    /// Get the sum of all validator weights
+    pub fn get_total_validator_weight(&self) -> u64 {
+        self.validators
+            .get_or_default()
+            .iter()
+            .map(|v| self.validator_weights.get(v).unwrap_or_default())
+            .sum()
+    }
+
+    /// Get CSPR currently held in the liquidity buffer
+    pub fn get_liquidity_buffer(&self) -> U512 {
+        self.liquidity_buffer.get_or_default()
+    }
+
+    /// Get the target liquidity buffer size in CSPR
+    pub fn get_liquidity_buffer_target(&self) -> U512 {
+        let target_bps = self.buffer_target_bps.get_or_default();
+        self.total_pooled_cspr.get_or_default() * U512::from(target_bps) / U512::from(BPS_PRECISION)
+    }
+
+    /// Get the liquidity buffer and instant unstake fee settings
+    pub fn get_liquidity_buffer_config(&self) -> LiquidityBufferConfig {
+        LiquidityBufferConfig {
+            target_bps: self.buffer_target_bps.get_or_default(),
+            stake_share_bps: self.buffer_stake_share_bps.get_or_default(),
+            min_fee_bps: self.instant_fee_min_bps.get_or_default(),
+            max_fee_bps: self.instant_fee_max_bps.get_or_default(),
+        }
+    }
+
+    /// Quote the instant unstake fee (basis points) for a CSPR amount
+    ///
+    /// The fee grows linearly from the minimum to the maximum fee as the
+    /// buffer left after the payout falls from the target down to zero.
+    pub fn get_instant_unstake_fee_bps(&self, cspr_amount: U512) -> u64 {
+        let min_fee = self.instant_fee_min_bps.get_or_default();
+        let max_fee = self.instant_fee_max_bps.get_or_default();
+        let target = self.get_liquidity_buffer_target();
+        let remaining = self.liquidity_buffer.get_or_default().saturating_sub(cspr_amount);
+
+        if target == U512::zero() {
+            return max_fee;
+        }
+        if remaining >= target {
+            return min_fee;
+        }
+
+        let depletion_bps = ((target - remaining) * U512::from(BPS_PRECISION) / target).as_u64();
+        min_fee + (max_fee - min_fee) * depletion_bps / BPS_PRECISION
+    }
+
+    /// Get the in-flight validator migration, if any
+    pub fn get_migration(&self) -> Option<ValidatorMigration> {
+        self.migration.get().flatten()
+    }
+
+    /// Get CSPR unbonding from a migrated validator, waiting to be re-delegated
+    pub fn get_pending_redelegation(&self) -> U512 {
+        self.pending_redelegation.get_or_default()
+    }
+
+    /// Get staked CSPR waiting to be delegated in the next batch
+    pub fn get_pending_delegation(&self) -> U512 {
+        self.pending_delegation.get_or_default()
+    }
+
+    /// Get the pending delegation amount that triggers a batch delegation
+    pub fn get_delegation_threshold(&self) -> U512 {
+        self.delegation_threshold.get_or_default()
+    }
+
+    /// Get what a keeper needs to decide whether calling `compound` pays off
+    pub fn get_compound_info(&self) -> CompoundInfo {
+        let pending_rewards = self.get_pending_rewards();
+        CompoundInfo {
+            pending_rewards,
+            last_compound_timestamp: self.last_compound_timestamp.get_or_default(),
+            next_compound_timestamp: self.get_next_compound_timestamp(),
+            expected_keeper_reward: self.keeper_reward_for(pending_rewards),
+        }
+    }
+
+    /// Get the compound keeper reward settings
+    pub fn get_keeper_reward_config(&self) -> KeeperRewardConfig {
+        KeeperRewardConfig {
+            reward_bps: self.keeper_reward_bps.get_or_default(),
+            max_reward: self.keeper_max_reward.get_or_default(),
+            min_interval_ms: self.compound_min_interval_ms.get_or_default(),
+        }
+    }
+
+    /// Get the protocol fee recipients (empty: everything goes to the treasury)
+    pub fn get_fee_recipients(&self) -> Vec<FeeRecipient> {
+        self.fee_splitter.get_recipients()
+    }
+
+    /// Get the total protocol fees paid to `account` so far (CSPR value)
+    pub fn get_fee_accrued(&self, account: Address) -> U512 {
+        self.fee_splitter.get_accrued(account)
+    }
+
+    /// Get the total CSPR staked with `referrer` as referral
+    pub fn get_referral_total(&self, referrer: Address) -> U512 {
+        self.referral_totals.get(&referrer).unwrap_or_default()
+    }
+
+    /// Get the treasury address
+    pub fn get_treasury(&self) -> Option<Address> {
+        self.treasury.get()
+    }
+
+    /// Get the admin address
+    pub fn get_admin(&self) -> Option<Address> {
+        self.admin.get()
+    }
+
+    /// Get the proposed admin waiting to accept, if any
+    pub fn get_pending_admin(&self) -> Option<Address> {
+        self.pending_admin.get().flatten()
+    }
+
+    /// Check whether `account` has been granted `role`
+    pub fn has_role(&self, role: Role, account: Address) -> bool {
+        self.roles.has_role(role, account)
+    }
+
+    /// Check whether `compound` is restricted to keepers
+    pub fn is_keeper_only_compound(&self) -> bool {
+        self.keeper_only_compound.get_or_default()
+    }
+
+    /// Get the delay before queued parameter changes can execute (zero = instant)
+    pub fn get_timelock_delay(&self) -> u64 {
+        self.timelock.get_delay()
+    }
+
+    /// Get a queued parameter change by id
+    pub fn get_queued_operation(&self, id: u64) -> Option<QueuedOperation> {
+        self.timelock.get_operation(id)
+    }
+
+    /// Get all parameter changes waiting for execution
+    pub fn get_queued_operations(&self) -> Vec<QueuedOperation> {
+        self.timelock.get_queued_operations()
+    }
+
+    // ============ INTERNAL FUNCTIONS ============
+
+    /// thCSPR worth `cspr_amount`
+    ///
+    /// Round down for shares minted and up for shares burned (see `share_math`).
+    fn cspr_to_thcspr(&self, cspr_amount: U512, rounding: Rounding) -> U512 {
+        let total_pooled = self.total_pooled_cspr.get_or_default();
+        let total_supply = self.total_thcspr_supply.get_or_default();
+
+        share_math::to_shares(cspr_amount, total_pooled, total_supply, rounding)
+    }
+
+    /// CSPR released by burning thCSPR (rounds down, see `share_math`)
+    fn thcspr_to_cspr(&self, thcspr_amount: U512) -> U512 {
+        let total_pooled = self.total_pooled_cspr.get_or_default();
+        let total_supply = self.total_thcspr_supply.get_or_default();
+
+        if total_supply == U512::zero() {
+            return U512::zero();
+        }
+
+        // The virtual offset can push the value of the last shares of a pool
+        // that took a loss slightly above what is left
+        share_math::to_assets(thcspr_amount, total_pooled, total_supply, Rounding::Down)
+            .min(total_pooled)
+    }
+
+    fn require_not_paused(&self) {
+        if self.is_paused.get_or_default() {
+            self.env().revert(Error::ContractPaused);
+        }
+    }
+
+    /// Revert if staking `cspr_amount` for `recipient` breaks the allowlist or a cap
+    fn require_within_caps(&self, recipient: Address, cspr_amount: U512) {
+        if self.allowlist_enabled.get_or_default() && !self.is_allowlisted(recipient) {
+            self.env().revert(Error::NotAllowlisted);
+        }
+
+        let global_cap = self.stake_cap.get_or_default();
+        if global_cap > U512::zero()
+            && self.total_pooled_cspr.get_or_default() + cspr_amount > global_cap
+        {
+            self.env().revert(Error::StakeCapExceeded);
+        }
+
+        let address_cap = self.per_address_cap.get_or_default();
+        if address_cap > U512::zero() && !self.is_cap_exempt(recipient) {
+            let position = self.thcspr_to_cspr(self.get_thcspr_balance(recipient));
+            if position + cspr_amount > address_cap {
+                self.env().revert(Error::AddressCapExceeded);
+            }
+        }
+    }
+
+    /// Mint thCSPR to `recipient` for the attached CSPR
+    fn stake_internal(&mut self, recipient: Address, referral: Option<Address>) -> U512 {
+        self.require_not_paused();
+
+        let cspr_amount = self.env().attached_value();
+
+        // Validate minimum
+        let min = self.min_stake.get_or_default();
+        if cspr_amount < min {
+            self.env().revert(Error::BelowMinimumStake);
+        }
+
+        self.require_within_caps(recipient, cspr_amount);
+
+        // Calculate thCSPR to mint
+        let thcspr_amount = self.cspr_to_thcspr(cspr_amount, Rounding::Down);
+        if thcspr_amount == U512::zero() {
+            self.env().revert(Error::ZeroSharesMinted);
+        }
+
+        // Update state BEFORE external calls (CEI pattern)
+        let new_total_pooled = self.total_pooled_cspr.get_or_default() + cspr_amount;
+        let new_total_supply = self.total_thcspr_supply.get_or_default() + thcspr_amount;
+        self.total_pooled_cspr.set(new_total_pooled);
+        self.total_thcspr_supply.set(new_total_supply);
+
+        // Keep part of the stake liquid for instant unstakes
+        let to_buffer = self.buffer_share_of_stake(cspr_amount);
+        if to_buffer > U512::zero() {
+            let buffer = self.liquidity_buffer.get_or_default();
+            self.liquidity_buffer.set(buffer + to_buffer);
+        }
+
+        if let Some(referrer) = referral {
+            let total = self.referral_totals.get(&referrer).unwrap_or_default();
+            self.referral_totals.set(&referrer, total + cspr_amount);
+        }
+
+        // Mint thCSPR to user via cross-contract call
+        self.mint_thcspr(recipient, thcspr_amount);
+
+        // Batch CSPR for delegation, delegating once the threshold is reached
+        let to_delegate = cspr_amount - to_buffer;
+        if to_delegate > U512::zero() {
+            let pending = self.pending_delegation.get_or_default() + to_delegate;
+            self.pending_delegation.set(pending);
+            if pending >= self.delegation_threshold.get_or_default() {
+                self.flush_pending_delegation();
+            }
+        }
+
+        // Emit event
+        self.env().emit_event(Staked {
+            user: recipient,
+            cspr_amount,
+            thcspr_minted: thcspr_amount,
+            exchange_rate: self.get_exchange_rate(),
+            referrer: referral,
+        });
+
+        thcspr_amount
+    }
+
+    fn require_admin(&self) {
+        let admin = self.admin.get().unwrap_or_revert_with(&self.env(), Error::AdminNotSet);
+        if self.env().caller() != admin {
+            self.env().revert(Error::NotAdmin);
+        }
+    }
+
+    fn require_known_validator(&self, validator: &PublicKey) {
+        if !self.validators.get_or_default().contains(validator) {
+            self.env().revert(Error::ValidatorNotFound);
+        }
+    }
+
+    fn require_can_add_validator(&self, validator: &PublicKey) {
+        let validators = self.validators.get_or_default();
+        if validators.contains(validator) {
+            self.env().revert(Error::ValidatorAlreadyExists);
+        }
+        if validators.len() >= MAX_VALIDATORS {
+            self.env().revert(Error::TooManyValidators);
+        }
+    }
+
+    fn require_can_remove_validator(&self, validator: &PublicKey) {
+        self.require_known_validator(validator);
+        if self.env().delegated_amount(validator.clone()) > U512::zero() {
+            self.env().revert(Error::ValidatorHasDelegation);
+        }
+    }
+
+    /// Apply `call` now if there is no timelock delay, otherwise queue it
+    fn schedule(&mut self, call: TimelockedCall) {
+        if self.timelock.get_delay() == 0 {
+            self.apply_call(call);
+            return;
+        }
+        let now = self.env().get_block_time();
+        let operation = self.timelock.queue(call, now);
+        self.env().emit_event(OperationQueued {
+            id: operation.id,
+            eta: operation.eta,
+        });
+    }
+
+    /// Apply a timelocked parameter change
+    fn apply_call(&mut self, call: TimelockedCall) {
+        match call {
+            TimelockedCall::SetProtocolFee { fee_bps } => {
+                let old_fee = self.protocol_fee_bps.get_or_default();
+                self.protocol_fee_bps.set(fee_bps);
+                self.env().emit_event(FeeUpdated {
+                    old_fee_bps: old_fee,
+                    new_fee_bps: fee_bps,
+                });
+            }
+            TimelockedCall::SetValidator { validator } => {
+                let primary = self
+                    .validators
+                    .get_or_default()
+                    .first()
+                    .cloned()
+                    .unwrap_or_revert_with(&self.env(), Error::ValidatorNotSet);
+                if primary != validator {
+                    self.migrate_validator(primary, validator);
+                }
+            }
+            TimelockedCall::StartValidatorMigration { from, to } => {
+                self.migrate_validator(from, to);
+            }
+            TimelockedCall::AddValidator { validator, weight } => {
+                // The set may have changed while the call was queued
+                self.require_can_add_validator(&validator);
+                let mut validators = self.validators.get_or_default();
+                validators.push(validator.clone());
+                self.validators.set(validators);
+                self.validator_weights.set(&validator, weight);
+                self.env().emit_event(ValidatorAdded { validator, weight });
+            }
+            TimelockedCall::RemoveValidator { validator } => {
+                self.require_can_remove_validator(&validator);
+                // Keep track of an evicted delegation once the validator is gone
+                self.book_evicted_delegations();
+                let mut validators = self.validators.get_or_default();
+                validators.retain(|v| *v != validator);
+                self.validators.set(validators);
+                self.validator_weights.set(&validator, 0);
+                self.env().emit_event(ValidatorRemoved { validator });
+            }
+            TimelockedCall::SetValidatorWeight { validator, weight } => {
+                self.require_known_validator(&validator);
+                let old_weight = self.validator_weights.get(&validator).unwrap_or_default();
+                self.validator_weights.set(&validator, weight);
+                self.env().emit_event(ValidatorWeightUpdated {
+                    validator,
+                    old_weight,
+                    new_weight: weight,
+                });
+            }
+            TimelockedCall::SetThcsprToken { thcspr_token } => {
+                self.thcspr_token.set(thcspr_token);
+            }
+            TimelockedCall::SetTreasury { treasury } => {
+                self.treasury.set(treasury);
+            }
+            TimelockedCall::SetFeeMode { mode } => {
+                let old_mode = self.get_fee_mode();
+                self.fee_mode.set(mode.clone());
+                self.env().emit_event(FeeModeUpdated {
+                    old_mode,
+                    new_mode: mode,
+                });
+            }
+            TimelockedCall::SetFeeRecipients { recipients } => {
+                self.env().emit_event(FeeRecipientsUpdated {
+                    accounts: recipients.iter().map(|r| r.account).collect(),
+                    shares_bps: recipients.iter().map(|r| r.share_bps).collect(),
+                });
+                self.fee_splitter.set_recipients(recipients);
+            }
+            TimelockedCall::SetKeeperRewardConfig {
+                reward_bps,
+                max_reward,
+                min_interval_ms,
+            } => {
+                self.keeper_reward_bps.set(reward_bps);
+                self.keeper_max_reward.set(max_reward);
+                self.compound_min_interval_ms.set(min_interval_ms);
+                self.env().emit_event(KeeperRewardConfigUpdated {
+                    reward_bps,
+                    max_reward,
+                    min_interval_ms,
+                });
+            }
+            TimelockedCall::SetMinStake { min_stake } => {
+                self.min_stake.set(min_stake);
+            }
+            TimelockedCall::SetStakeCaps {
+                global_cap,
+                per_address_cap,
+            } => {
+                self.stake_cap.set(global_cap);
+                self.per_address_cap.set(per_address_cap);
+                self.env().emit_event(StakeCapsUpdated {
+                    global_cap,
+                    per_address_cap,
+                });
+            }
+            TimelockedCall::SetCapExempt { account, exempt } => {
+                self.cap_exempt.set(&account, exempt);
+                self.env().emit_event(CapExemptionUpdated { account, exempt });
+            }
+            TimelockedCall::SetDelegationThreshold { threshold } => {
+                let old_threshold = self.delegation_threshold.get_or_default();
+                self.delegation_threshold.set(threshold);
+                self.env().emit_event(DelegationThresholdUpdated {
+                    old_threshold,
+                    new_threshold: threshold,
+                });
+            }
+            TimelockedCall::SetUnbondingDelay { delay_ms } => {
+                let old_delay_ms = self.unbonding_delay_ms.get_or_default();
+                self.unbonding_delay_ms.set(delay_ms);
+                self.env().emit_event(UnbondingDelayUpdated {
+                    old_delay_ms,
+                    new_delay_ms: delay_ms,
+                });
+            }
+            TimelockedCall::SetLiquidityBufferConfig {
+                target_bps,
+                stake_share_bps,
+                min_fee_bps,
+                max_fee_bps,
+            } => {
+                self.buffer_target_bps.set(target_bps);
+                self.buffer_stake_share_bps.set(stake_share_bps);
+                self.instant_fee_min_bps.set(min_fee_bps);
+                self.instant_fee_max_bps.set(max_fee_bps);
+                self.env().emit_event(LiquidityBufferConfigUpdated {
+                    target_bps,
+                    stake_share_bps,
+                    min_fee_bps,
+                    max_fee_bps,
+                });
+            }
+            TimelockedCall::SetTimelockDelay { delay_ms } => {
+                let old_delay_ms = self.timelock.get_delay();
+                self.timelock.set_delay(delay_ms);
+                self.env().emit_event(TimelockDelayUpdated {
+                    old_delay_ms,
+                    new_delay_ms: delay_ms,
+                });
+            }
+            TimelockedCall::SetLendingConfig { .. }
+            | TimelockedCall::SetInterestRateModel { .. }
+            | TimelockedCall::SetReserveFactor { .. }
+            | TimelockedCall::SetStakingApr { .. }
+            | TimelockedCall::WithdrawReserves { .. } => {
+                self.env().revert(Error::UnsupportedTimelockCall)
+            }
+        }
+    }
+
+    /// Revert unless the caller has `role` or is the admin
+    fn require_role(&self, role: Role) {
+        let caller = self.env().caller();
+        if self.admin.get() == Some(caller) || self.roles.has_role(role, caller) {
+            return;
+        }
+        self.env().revert(Error::MissingRole);
+    }
+
+    fn require_unstake_balance(&self, user: Address, thcspr_amount: U512) {
+        // Validate amount
+        if thcspr_amount == U512::zero() {
+            self.env().revert(Error::AmountMustBePositive);
+        }
+
+        // Validate user has sufficient thCSPR balance
+        let balance = self.get_thcspr_balance(user);
+        if balance < thcspr_amount {
+            self.env().revert(Error::InsufficientBalance);
+        }
+    }
+
+    /// Burn thCSPR, undelegate the CSPR and queue a withdrawal request
+    ///
+    /// Losses are socialized first so the request is priced at what actually
+    /// backs the pool. Reverts with `InsufficientBacking` if the CSPR cannot
+    /// be sourced anyway.
+    fn queue_withdrawal(&mut self, user: Address, thcspr_amount: U512) -> u64 {
+        self.sync();
+
+        // Calculate CSPR to return
+        let cspr_amount = self.thcspr_to_cspr(thcspr_amount);
+
+        // Update state BEFORE external calls (CEI pattern)
+        let new_total_pooled = self.total_pooled_cspr.get_or_default() - cspr_amount;
+        let new_total_supply = self.total_thcspr_supply.get_or_default() - thcspr_amount;
+        self.total_pooled_cspr.set(new_total_pooled);
+        self.total_thcspr_supply.set(new_total_supply);
+
+        // Burn thCSPR via cross-contract call
+        self.burn_thcspr(user, thcspr_amount);
+
+        // Stakes not yet delegated are used first, the rest is undelegated
+        let pending_delegation = self.pending_delegation.get_or_default();
+        let from_pending = pending_delegation.min(cspr_amount);
+        self.pending_delegation.set(pending_delegation - from_pending);
+
+        // Undelegate from validator via System Auction
+        let to_undelegate = cspr_amount - from_pending;
+        let undelegated = if to_undelegate > U512::zero() {
+            self.undelegate_from_validator(to_undelegate)
+        } else {
+            U512::zero()
+        };
+
+        // CSPR already unbonding back to the pool (or for a validator
+        // migration) covers any shortfall, then the liquidity buffer
+        let mut shortfall = to_undelegate - undelegated;
+        if shortfall > U512::zero() {
+            let unbonding = self.pool_unbonding.get_or_default();
+            let covered = unbonding.min(shortfall);
+            self.pool_unbonding.set(unbonding - covered);
+            shortfall -= covered;
+        }
+        if shortfall > U512::zero() {
+            let pending = self.pending_redelegation.get_or_default();
+            let covered = pending.min(shortfall);
+            self.pending_redelegation.set(pending - covered);
+            shortfall -= covered;
+        }
+        if shortfall > U512::zero() {
+            let buffer = self.liquidity_buffer.get_or_default();
+            if buffer < shortfall {
+                self.env().revert(Error::InsufficientBacking);
+            }
+            self.liquidity_buffer.set(buffer - shortfall);
+        }
+
+        // Create withdrawal request
+        let withdrawal_id = self.withdrawal_counter.get_or_default();
+        self.withdrawal_counter.set(withdrawal_id + 1);
+
+        let now = self.env().get_block_time();
+        let claimable = now + self.unbonding_delay_ms.get_or_default();
+
+        let request = WithdrawalRequest {
+            id: withdrawal_id,
+            user,
+            cspr_amount,
+            thcspr_burned: thcspr_amount,
+            request_timestamp: now,
+            claimable_timestamp: claimable,
+            claimed: false,
+            loss_index: self.get_withdrawal_loss_index(),
+        };
+
+        self.withdrawals.set(&withdrawal_id, request);
+        let pending_withdrawals = self.total_pending_withdrawals.get_or_default() + cspr_amount;
+        self.total_pending_withdrawals.set(pending_withdrawals);
+
+        // Track user's withdrawals
+        let mut user_ids = self.user_withdrawals.get(&user).unwrap_or_default();
+        user_ids.push(withdrawal_id);
+        self.user_withdrawals.set(&user, user_ids);
+
+        // Emit event
+        self.env().emit_event(Unstaked {
+            user,
+            thcspr_burned: thcspr_amount,
+            cspr_amount,
+            withdrawal_id,
+            claimable_timestamp: claimable,
+        });
+
+        withdrawal_id
+    }
+
+    /// CSPR a withdrawal request pays out after socialized losses
+    fn withdrawal_payout(&self, request: &WithdrawalRequest) -> U512 {
+        if request.loss_index == U512::zero() {
+            return request.cspr_amount;
+        }
+        request.cspr_amount * self.get_withdrawal_loss_index() / request.loss_index
+    }
+
+    /// CSPR backing the pool: delegations, batched stakes, CSPR unbonding
+    /// back and the buffer
+    fn get_pool_backing(&self) -> U512 {
+        self.get_delegated_amount()
+            + self.pending_delegation.get_or_default()
+            + self.pending_redelegation.get_or_default()
+            + self.pool_unbonding.get_or_default()
+            + self.liquidity_buffer.get_or_default()
+    }
+
+    /// Move CSPR that is back in the purse into the liquidity buffer
+    ///
+    /// Only the balance above everything already reserved (unclaimed
+    /// withdrawals, the buffer, CSPR awaiting re-delegation) is taken. The part
+    /// not already counted as pool CSPR unbonding (rewards earned by an
+    /// evicted delegation, CSPR written off earlier) is added to the pool.
+    fn sweep_returned_cspr(&mut self) {
+        let buffer = self.liquidity_buffer.get_or_default();
+        let reserved = self.total_pending_withdrawals.get_or_default()
+            + buffer
+            + self.pending_delegation.get_or_default()
+            + self.pending_redelegation.get_or_default();
+        let returned = self.env().self_balance().saturating_sub(reserved);
+        if returned == U512::zero() {
+            return;
+        }
+
+        let unbonding = self.pool_unbonding.get_or_default();
+        let from_unbonding = unbonding.min(returned);
+        self.pool_unbonding.set(unbonding - from_unbonding);
+        self.liquidity_buffer.set(buffer + returned);
+
+        let recovered = returned - from_unbonding;
+        if recovered > U512::zero() {
+            let total_pooled = self.total_pooled_cspr.get_or_default();
+            self.total_pooled_cspr.set(total_pooled + recovered);
+        }
+    }
+
+    /// Delegate all batched stakes in one go
+    fn flush_pending_delegation(&mut self) -> U512 {
+        let amount = self.pending_delegation.get_or_default();
+        self.pending_delegation.set(U512::zero());
+        self.delegate_to_validator(amount);
+        self.env().emit_event(DelegationsFlushed { amount });
+        amount
+    }
+
+    /// Earliest block time at which `compound` can be called
+    fn get_next_compound_timestamp(&self) -> u64 {
+        match self.last_compound_timestamp.get() {
+            Some(last) => last + self.compound_min_interval_ms.get_or_default(),
+            None => 0,
+        }
+    }
+
+    /// Keeper share of `rewards`, capped at the maximum keeper reward
+    fn keeper_reward_for(&self, rewards: U512) -> U512 {
+        let reward_bps = self.keeper_reward_bps.get_or_default();
+        let reward = rewards * U512::from(reward_bps) / U512::from(BPS_PRECISION);
+        reward.min(self.keeper_max_reward.get_or_default())
+    }
+
+    /// Add rewards to the pool and mint thCSPR worth each payout
+    ///
+    /// Payouts are priced before they join the pool, so each recipient gets
+    /// shares worth its CSPR amount while the CSPR itself stays delegated.
+    fn mint_reward_shares(&mut self, rewards_to_pool: U512, payouts: Vec<(Address, U512)>) {
+        let total_pooled = self.total_pooled_cspr.get_or_default() + rewards_to_pool;
+        let total_supply = self.total_thcspr_supply.get_or_default();
+
+        let mut paid = U512::zero();
+        let mut minted = U512::zero();
+        for (recipient, amount) in payouts {
+            let shares = share_math::to_shares(amount, total_pooled, total_supply, Rounding::Down);
+            if shares > U512::zero() {
+                self.mint_thcspr(recipient, shares);
+            }
+            paid += amount;
+            minted += shares;
+        }
+
+        self.total_pooled_cspr.set(total_pooled + paid);
+        self.total_thcspr_supply.set(total_supply + minted);
+    }
+
+    /// Split a protocol fee between the fee recipients
+    ///
+    /// Everything goes to the treasury while no recipients are configured.
+    fn split_protocol_fee(&self, amount: U512) -> Vec<(Address, U512)> {
+        if self.fee_splitter.get_recipients().is_empty() {
+            let treasury = self
+                .treasury
+                .get()
+                .unwrap_or_revert_with(&self.env(), Error::TreasuryNotSet);
+            return vec![(treasury, amount)];
+        }
+        self.fee_splitter.split(amount)
+    }
+
+    /// Pay CSPR out of the pool's liquid CSPR (batched stakes, then the buffer)
+    fn pay_from_liquidity(&mut self, to: Address, amount: U512) {
+        let pending = self.pending_delegation.get_or_default();
+        let buffer = self.liquidity_buffer.get_or_default();
+        if amount > pending + buffer {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+
+        let from_pending = pending.min(amount);
+        self.pending_delegation.set(pending - from_pending);
+        self.liquidity_buffer.set(buffer - (amount - from_pending));
+
+        self.env().transfer_tokens(&to, &amount);
+    }
+
+    /// Part of a new stake that tops up the liquidity buffer
+    fn buffer_share_of_stake(&self, cspr_amount: U512) -> U512 {
+        let share_bps = self.buffer_stake_share_bps.get_or_default();
+        if share_bps == 0 {
+            return U512::zero();
+        }
+        let room = self
+            .get_liquidity_buffer_target()
+            .saturating_sub(self.liquidity_buffer.get_or_default());
+        let share = cspr_amount * U512::from(share_bps) / U512::from(BPS_PRECISION);
+        share.min(room)
+    }
+
+    /// Mint thCSPR to user via cross-contract call
+    fn mint_thcspr(&self, to: Address, amount: U512) {
+        let token_address = self
+            .thcspr_token
+            .get()
+            .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
+        ThCsprTokenContractRef::new(self.env(), token_address).mint(to, amount);
+    }
+
+    /// Reference to the linked thCSPR token contract
+    fn thcspr_token_ref(&self) -> ThCsprTokenContractRef {
+        let token_address = self
+            .thcspr_token
+            .get()
+            .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
+        ThCsprTokenContractRef::new(self.env(), token_address)
+    }
+
+    /// Burn thCSPR from user via cross-contract call
+    fn burn_thcspr(&self, from: Address, amount: U512) {
+        let token_address = self
+            .thcspr_token
+            .get()
+            .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
+        ThCsprTokenContractRef::new(self.env(), token_address).burn(from, amount);
+    }
+
+    /// Get thCSPR balance of a user via cross-contract call
+    fn get_thcspr_balance(&self, user: Address) -> U512 {
+        let token_address = self
             .thcspr_token
             .get()
             .unwrap_or_revert_with(&self.env(), Error::TokenNotSet);
@@ -414,55 +1709,238 @@ impl ThawCore {
 
     // ============ DELEGATION FUNCTIONS (using Odra native methods) ============
 
-    /// Delegate CSPR to validator using Odra's native delegation
+    /// Delegate CSPR across the validator set using Odra's native delegation
     ///
     /// Uses `self.env().delegate()` which internally calls the System Auction
-    /// with the correct address for the current network.
+    /// with the correct address for the current network. The amount is split
+    /// so that each validator moves towards its target weight: validators below
+    /// their target receive a share proportional to their shortfall.
     ///
     /// # Arguments
     /// * `amount` - Amount of CSPR to delegate (in motes)
-    fn delegate_to_validator(&self, amount: U512) {
-        let validator = self
-            .validator_public_key
-            .get()
-            .unwrap_or_revert_with(&self.env(), Error::ValidatorNotSet);
+    fn delegate_to_validator(&mut self, amount: U512) {
+        let validators = self.validators.get_or_default();
+        let total_weight = self.get_total_validator_weight();
+        if total_weight == 0 {
+            self.env().revert(Error::NoActiveValidators);
+        }
+
+        let delegated: Vec<U512> = validators
+            .iter()
+            .map(|v| self.env().delegated_amount(v.clone()))
+            .collect();
+        let total_after = delegated.iter().fold(U512::zero(), |acc, d| acc + *d) + amount;
+
+        // Shortfall of each validator against its target after this delegation
+        let deficits: Vec<U512> = validators
+            .iter()
+            .zip(delegated.iter())
+            .map(|(v, current)| {
+                let weight = self.validator_weights.get(v).unwrap_or_default();
+                let target = total_after * U512::from(weight) / U512::from(total_weight);
+                target.saturating_sub(*current)
+            })
+            .collect();
+
+        let mut shares = split_proportionally(amount, &deficits);
+
+        // Rounding dust goes to the first validator with a non-zero weight
+        let allocated = shares.iter().fold(U512::zero(), |acc, s| acc + *s);
+        if allocated < amount {
+            let first_active = validators
+                .iter()
+                .position(|v| self.validator_weights.get(v).unwrap_or_default() > 0)
+                .unwrap_or_revert_with(&self.env(), Error::NoActiveValidators);
+            shares[first_active] += amount - allocated;
+        }
 
         // Use Odra's native delegation method
         // This internally uses system::get_auction() to get the correct auction address
-        self.env().delegate(validator, amount);
+        for (validator, share) in validators.into_iter().zip(shares) {
+            if share > U512::zero() {
+                self.env().delegate(validator.clone(), share);
+                self.record_delegation(&validator);
+            }
+        }
     }
 
-    /// Undelegate CSPR from validator using Odra's native undelegation
+    /// Undelegate CSPR from the validator set using Odra's native undelegation
     ///
     /// Uses `self.env().undelegate()` which internally calls the System Auction.
+    /// Validators above their target weight are drained first, so a validator
+    /// with zero weight is emptied before the others are touched.
     ///
     /// # Arguments
     /// * `amount` - Amount of CSPR to undelegate (in motes)
     ///
+    /// # Returns
+    /// Amount actually undelegated
+    ///
     /// # Note
-    /// Unbonding takes 14 hours (1 era) on Casper 2.0
-    fn undelegate_from_validator(&self, amount: U512) {
-        let validator = self
-            .validator_public_key
-            .get()
-            .unwrap_or_revert_with(&self.env(), Error::ValidatorNotSet);
+    /// Unbonding takes 14 hours (7 eras) on Casper 2.0. If less than `amount` is
+    /// still delegated, the auction has already unbonded the rest (a delegation
+    /// that fell below the minimum) and only what is left gets undelegated.
+    /// Anything the auction unbonds on top of the requested shares is tracked
+    /// as pool CSPR unbonding back to the contract.
+    fn undelegate_from_validator(&mut self, amount: U512) -> U512 {
+        let validators = self.validators.get_or_default();
+        if validators.is_empty() {
+            self.env().revert(Error::ValidatorNotSet);
+        }
+        let total_weight = self.get_total_validator_weight();
+
+        let delegated: Vec<U512> = validators
+            .iter()
+            .map(|v| self.env().delegated_amount(v.clone()))
+            .collect();
+        let total_delegated = delegated.iter().fold(U512::zero(), |acc, d| acc + *d);
+        let total_after = total_delegated.saturating_sub(amount);
+
+        // Excess of each validator over its target after this undelegation
+        let excesses: Vec<U512> = validators
+            .iter()
+            .zip(delegated.iter())
+            .map(|(v, current)| {
+                let weight = self.validator_weights.get(v).unwrap_or_default();
+                let target = if total_weight == 0 {
+                    U512::zero()
+                } else {
+                    total_after * U512::from(weight) / U512::from(total_weight)
+                };
+                current.saturating_sub(target)
+            })
+            .collect();
+
+        let mut shares = split_proportionally(amount, &excesses);
+
+        // Cover rounding dust from whatever is still delegated
+        let allocated = shares.iter().fold(U512::zero(), |acc, s| acc + *s);
+        let undelegated = amount.min(total_delegated);
+        let mut remaining = undelegated - allocated;
+        for (share, current) in shares.iter_mut().zip(delegated.iter()) {
+            if remaining == U512::zero() {
+                break;
+            }
+            let extra = (*current - *share).min(remaining);
+            *share += extra;
+            remaining -= extra;
+        }
 
         // Use Odra's native undelegation method
-        self.env().undelegate(validator, amount);
+        let mut forced = U512::zero();
+        for ((validator, share), current) in validators.into_iter().zip(shares).zip(delegated) {
+            if share > U512::zero() {
+                self.env().undelegate(validator.clone(), share);
+                let left = self.env().delegated_amount(validator.clone());
+                forced += (current - share).saturating_sub(left);
+                self.known_delegations.set(&validator, left);
+            }
+        }
+
+        if forced > U512::zero() {
+            self.add_pool_unbonding(forced);
+        }
+
+        undelegated
     }
 
-    /// Get currently delegated amount from validator
+    /// Get currently delegated amount across the validator set
     ///
     /// # Returns
     /// Amount of CSPR currently delegated in motes
     fn get_delegated_amount(&self) -> U512 {
-        let validator = self
-            .validator_public_key
-            .get()
-            .unwrap_or_revert_with(&self.env(), Error::ValidatorNotSet);
+        // Use Odra's native method to get delegated amount per validator
+        self.validators
+            .get_or_default()
+            .into_iter()
+            .fold(U512::zero(), |acc, v| acc + self.env().delegated_amount(v))
+    }
+
+    /// Remember what is delegated to `validator` after a delegation change
+    fn record_delegation(&mut self, validator: &PublicKey) {
+        let delegated = self.env().delegated_amount(validator.clone());
+        self.known_delegations.set(validator, delegated);
+    }
+
+    /// Book the delegations of evicted validators as pool CSPR unbonding
+    ///
+    /// The auction unbonds every delegation to an evicted validator back to
+    /// the contract, so a delegation that dropped to zero without being
+    /// undelegated is on its way back, not lost.
+    fn book_evicted_delegations(&mut self) {
+        for validator in self.validators.get_or_default() {
+            let known = self.known_delegations.get(&validator).unwrap_or_default();
+            let delegated = self.env().delegated_amount(validator.clone());
+            if delegated == U512::zero() && known > U512::zero() {
+                self.add_pool_unbonding(known);
+                self.env().emit_event(ValidatorEvicted {
+                    validator: validator.clone(),
+                    amount: known,
+                });
+            }
+            self.known_delegations.set(&validator, delegated);
+        }
+    }
+
+    /// Track `amount` of pool CSPR unbonding back to the contract
+    ///
+    /// `sync` writes off whatever has not come back once the unbonding delay
+    /// has passed.
+    fn add_pool_unbonding(&mut self, amount: U512) {
+        self.pool_unbonding
+            .set(self.pool_unbonding.get_or_default() + amount);
+        let settles_at = self.env().get_block_time() + self.unbonding_delay_ms.get_or_default();
+        if settles_at > self.pool_unbonding_settles_at.get_or_default() {
+            self.pool_unbonding_settles_at.set(settles_at);
+        }
+    }
+
+    /// Undelegate everything from `from` and queue it for re-delegation to `to`
+    fn migrate_validator(&mut self, from: PublicKey, to: PublicKey) {
+        if self.migration.get().flatten().is_some() {
+            self.env().revert(Error::MigrationInProgress);
+        }
+        self.book_evicted_delegations();
+
+        let mut validators = self.validators.get_or_default();
+        let index = validators
+            .iter()
+            .position(|v| *v == from)
+            .unwrap_or_revert_with(&self.env(), Error::ValidatorNotFound);
+
+        // Hand the weight of `from` over to `to`
+        let weight = self.validator_weights.get(&from).unwrap_or_default();
+        validators.remove(index);
+        if !validators.contains(&to) {
+            validators.push(to.clone());
+        }
+        let to_weight = self.validator_weights.get(&to).unwrap_or_default();
+        self.validator_weights.set(&to, to_weight + weight);
+        self.validator_weights.set(&from, 0);
+        self.validators.set(validators);
+
+        let amount = self.env().delegated_amount(from.clone());
+        self.env().emit_event(ValidatorMigrationStarted {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        });
+
+        if amount == U512::zero() {
+            self.env().emit_event(ValidatorMigrationCompleted { from, to, amount });
+            return;
+        }
 
-        // Use Odra's native method to get delegated amount
-        self.env().delegated_amount(validator)
+        self.env().undelegate(from.clone(), amount);
+        self.known_delegations.set(&from, U512::zero());
+        self.pending_redelegation
+            .set(self.pending_redelegation.get_or_default() + amount);
+        self.migration.set(Some(ValidatorMigration {
+            from,
+            to,
+            amount,
+            started_timestamp: self.env().get_block_time(),
+        }));
     }
 
     /// Get pending staking rewards
@@ -472,11 +1950,13 @@ impl ThawCore {
     ///
     /// # Note
     /// This calculates rewards based on the difference between
-    /// total pooled CSPR and actual delegated amount
+    /// total pooled CSPR and actual delegated amount (including CSPR
+    /// unbonding back to the pool and the liquidity buffer). A negative
+    /// difference is a loss and is handled by `sync`.
     fn get_pending_rewards(&self) -> U512 {
         // In Odra's native approach, we track rewards through the difference
         // between what we expect (total_pooled) and what's actually delegated
-        let delegated = self.get_delegated_amount();
+        let delegated = self.get_pool_backing();
         let total_pooled = self.total_pooled_cspr.get_or_default();
 
         // Rewards = actual delegated amount - tracked pooled amount
@@ -501,7 +1981,7 @@ impl ThawCore {
     // ============ ADMIN FUNCTIONS ============
 
     pub fn pause(&mut self) {
-        self.require_admin();
+        self.require_role(PAUSER_ROLE);
         self.is_paused.set(true);
         self.env().emit_event(Paused {
             by: self.env().caller(),
@@ -509,7 +1989,7 @@ impl ThawCore {
     }
 
     pub fn unpause(&mut self) {
-        self.require_admin();
+        self.require_role(PAUSER_ROLE);
         self.is_paused.set(false);
         self.env().emit_event(Unpaused {
             by: self.env().caller(),
@@ -517,54 +1997,400 @@ impl ThawCore {
     }
 
     pub fn set_protocol_fee(&mut self, fee_bps: u64) {
-        self.require_admin();
+        self.require_role(FEE_MANAGER_ROLE);
         if fee_bps > MAX_FEE_BPS {
             self.env().revert(Error::FeeTooHigh);
         }
-        let old_fee = self.protocol_fee_bps.get_or_default();
-        self.protocol_fee_bps.set(fee_bps);
-        self.env().emit_event(FeeUpdated {
-            old_fee_bps: old_fee,
-            new_fee_bps: fee_bps,
-        });
+        self.schedule(TimelockedCall::SetProtocolFee { fee_bps });
+    }
+
+    /// Choose how the protocol fee is paid to the treasury (fee manager)
+    ///
+    /// Goes through the timelock.
+    pub fn set_fee_mode(&mut self, mode: FeeMode) {
+        self.require_role(FEE_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetFeeMode { mode });
+    }
+
+    /// Set the protocol fee recipients and their shares (fee manager)
+    ///
+    /// Shares must be non-zero and add up to 10000 bps. An empty list sends
+    /// all fees to the treasury. Goes through the timelock.
+    pub fn set_fee_recipients(&mut self, recipients: Vec<FeeRecipient>) {
+        self.require_role(FEE_MANAGER_ROLE);
+        if !is_valid_split(&recipients) {
+            self.env().revert(Error::InvalidFeeRecipients);
+        }
+        self.schedule(TimelockedCall::SetFeeRecipients { recipients });
     }
 
     pub fn set_min_stake(&mut self, min_stake: U512) {
-        self.require_admin();
-        self.min_stake.set(min_stake);
+        self.require_role(RISK_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetMinStake { min_stake });
     }
 
     pub fn set_treasury(&mut self, treasury: Address) {
-        self.require_admin();
-        self.treasury.set(treasury);
+        self.require_role(FEE_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetTreasury { treasury });
+    }
+
+    /// Set the global and per-address stake caps (risk manager)
+    ///
+    /// Caps are in CSPR; zero disables a cap. Lowering a cap below the
+    /// current total only blocks new stakes, existing positions are kept.
+    /// Goes through the timelock.
+    pub fn set_stake_caps(&mut self, global_cap: U512, per_address_cap: U512) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetStakeCaps {
+            global_cap,
+            per_address_cap,
+        });
+    }
+
+    /// Switch allowlist mode on or off (risk manager)
+    ///
+    /// Like pausing, it only restricts who can stake, so it takes effect
+    /// immediately.
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.allowlist_enabled.set(enabled);
+        self.env().emit_event(AllowlistModeUpdated { enabled });
+    }
+
+    /// Add or remove an address from the allowlist (risk manager)
+    pub fn set_allowlisted(&mut self, account: Address, allowed: bool) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.allowlist.set(&account, allowed);
+        self.env().emit_event(AllowlistUpdated { account, allowed });
     }
 
+    /// Exempt an address from the per-address cap, or revoke it (risk manager)
+    ///
+    /// Meant for contracts that stake on behalf of many users, such as the
+    /// lending pool looping leveraged positions. The global cap and the
+    /// allowlist still apply. Goes through the timelock.
+    pub fn set_cap_exempt(&mut self, account: Address, exempt: bool) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetCapExempt { account, exempt });
+    }
+
+    /// Set the pending delegation amount that triggers a batch delegation (risk manager)
+    ///
+    /// Stakes accumulate until the batch reaches `threshold`, keeping each
+    /// delegation above the auction minimum. Zero delegates every stake.
+    /// Goes through the timelock.
+    pub fn set_delegation_threshold(&mut self, threshold: U512) {
+        self.require_role(RISK_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetDelegationThreshold { threshold });
+    }
+
+    /// Configure the compound keeper reward (fee manager, goes through the timelock)
+    ///
+    /// # Arguments
+    /// * `reward_bps` - Keeper share of harvested rewards (at most 5%)
+    /// * `max_reward` - Upper bound on the keeper reward per compound (motes)
+    /// * `min_interval_ms` - Minimum time between two compounds
+    pub fn set_keeper_reward_config(
+        &mut self,
+        reward_bps: u64,
+        max_reward: U512,
+        min_interval_ms: u64,
+    ) {
+        self.require_role(FEE_MANAGER_ROLE);
+        if reward_bps > MAX_KEEPER_REWARD_BPS {
+            self.env().revert(Error::InvalidKeeperConfig);
+        }
+        self.schedule(TimelockedCall::SetKeeperRewardConfig {
+            reward_bps,
+            max_reward,
+            min_interval_ms,
+        });
+    }
+
+    /// Set the unbonding delay for new withdrawal requests (risk manager)
+    ///
+    /// Should match the auction unbonding period of the network (7 eras on
+    /// mainnet). Requests already queued keep their claimable timestamp.
+    /// Goes through the timelock.
+    pub fn set_unbonding_delay(&mut self, delay_ms: u64) {
+        self.require_role(RISK_MANAGER_ROLE);
+        if delay_ms == 0 || delay_ms > MAX_UNBONDING_DELAY_MS {
+            self.env().revert(Error::InvalidUnbondingDelay);
+        }
+        self.schedule(TimelockedCall::SetUnbondingDelay { delay_ms });
+    }
+
+    /// Configure the liquidity buffer and instant unstake fee curve (risk manager)
+    ///
+    /// Goes through the timelock.
+    ///
+    /// # Arguments
+    /// * `target_bps` - Target buffer size as a share of total pooled CSPR
+    /// * `stake_share_bps` - Share of each new stake kept in the buffer while below target
+    /// * `min_fee_bps` - Instant unstake fee when the buffer stays at or above target
+    /// * `max_fee_bps` - Instant unstake fee when the buffer would be fully drained
+    pub fn set_liquidity_buffer_config(
+        &mut self,
+        target_bps: u64,
+        stake_share_bps: u64,
+        min_fee_bps: u64,
+        max_fee_bps: u64,
+    ) {
+        self.require_role(RISK_MANAGER_ROLE);
+        if target_bps > BPS_PRECISION
+            || stake_share_bps > BPS_PRECISION
+            || min_fee_bps > max_fee_bps
+        {
+            self.env().revert(Error::InvalidBufferConfig);
+        }
+        if max_fee_bps > MAX_INSTANT_FEE_BPS {
+            self.env().revert(Error::FeeTooHigh);
+        }
+        self.schedule(TimelockedCall::SetLiquidityBufferConfig {
+            target_bps,
+            stake_share_bps,
+            min_fee_bps,
+            max_fee_bps,
+        });
+    }
+
+    /// Migrate the primary validator to `validator` (validator manager)
+    ///
+    /// See `start_validator_migration`. Goes through the timelock.
     pub fn set_validator(&mut self, validator: PublicKey) {
-        self.require_admin();
-        // WARNING: Changing validator requires migration strategy
-        self.validator_public_key.set(validator);
+        self.require_role(VALIDATOR_MANAGER_ROLE);
+        self.schedule(TimelockedCall::SetValidator { validator });
+    }
+
+    /// Start moving all delegation from `from` to `to` (validator manager)
+    ///
+    /// Undelegates everything from `from`, removes it from the set and hands
+    /// its weight to `to`. The undelegated CSPR stays counted in the pool while
+    /// it unbonds, so the exchange rate is unaffected. Once it is back, call
+    /// `complete_validator_migration` to re-delegate it to `to`. Goes through
+    /// the timelock.
+    pub fn start_validator_migration(&mut self, from: PublicKey, to: PublicKey) {
+        self.require_role(VALIDATOR_MANAGER_ROLE);
+        if from == to {
+            self.env().revert(Error::SameValidator);
+        }
+        self.schedule(TimelockedCall::StartValidatorMigration { from, to });
+    }
+
+    /// Add a validator to the delegation set (validator manager)
+    ///
+    /// Goes through the timelock.
+    ///
+    /// # Arguments
+    /// * `validator` - Public key of the validator
+    /// * `weight` - Relative target weight of the validator
+    pub fn add_validator(&mut self, validator: PublicKey, weight: u64) {
+        self.require_role(VALIDATOR_MANAGER_ROLE);
+        self.require_can_add_validator(&validator);
+        self.schedule(TimelockedCall::AddValidator { validator, weight });
     }
 
-    pub fn transfer_admin(&mut self, new_admin: Address) {
+    /// Remove a validator from the delegation set (validator manager)
+    ///
+    /// The validator must not hold any delegation. Set its weight to zero
+    /// first so that unstakes drain it before the other validators. Goes
+    /// through the timelock.
+    pub fn remove_validator(&mut self, validator: PublicKey) {
+        self.require_role(VALIDATOR_MANAGER_ROLE);
+        self.require_can_remove_validator(&validator);
+        self.schedule(TimelockedCall::RemoveValidator { validator });
+    }
+
+    /// Update the target weight of a validator (validator manager)
+    ///
+    /// Goes through the timelock.
+    pub fn set_validator_weight(&mut self, validator: PublicKey, weight: u64) {
+        self.require_role(VALIDATOR_MANAGER_ROLE);
+        self.require_known_validator(&validator);
+        self.schedule(TimelockedCall::SetValidatorWeight { validator, weight });
+    }
+
+    /// Propose a new admin (admin only)
+    ///
+    /// The current admin keeps control until `new_admin` calls
+    /// `accept_admin`. A new proposal replaces any pending one.
+    pub fn propose_admin(&mut self, new_admin: Address) {
         self.require_admin();
+        let current_admin = self.env().caller();
+        self.pending_admin.set(Some(new_admin));
+        self.env().emit_event(AdminTransferProposed {
+            current_admin,
+            pending_admin: new_admin,
+        });
+    }
+
+    /// Accept a pending admin transfer (proposed admin only)
+    pub fn accept_admin(&mut self) {
+        let new_admin = self
+            .get_pending_admin()
+            .unwrap_or_revert_with(&self.env(), Error::NoPendingAdmin);
+        if self.env().caller() != new_admin {
+            self.env().revert(Error::NotPendingAdmin);
+        }
         let old_admin = self.admin.get().unwrap_or_revert_with(&self.env(), Error::AdminNotSet);
         self.admin.set(new_admin);
+        self.pending_admin.set(None);
         self.env().emit_event(AdminTransferred {
             old_admin,
             new_admin,
         });
     }
 
+    /// Cancel a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(&mut self) {
+        self.require_admin();
+        let cancelled_admin = self
+            .get_pending_admin()
+            .unwrap_or_revert_with(&self.env(), Error::NoPendingAdmin);
+        self.pending_admin.set(None);
+        self.env().emit_event(AdminTransferCancelled { cancelled_admin });
+    }
+
+    /// Propose `new_minter` as the thCSPR minter (admin only)
+    ///
+    /// ThawCore stays the minter until `new_minter` accepts on the token,
+    /// e.g. through `accept_thcspr_minter` on a replacement ThawCore.
+    pub fn propose_thcspr_minter(&mut self, new_minter: Address) {
+        self.require_admin();
+        self.thcspr_token_ref().propose_minter(new_minter);
+    }
+
+    /// Cancel a pending thCSPR minter transfer (admin only)
+    pub fn cancel_thcspr_minter_transfer(&mut self) {
+        self.require_admin();
+        self.thcspr_token_ref().cancel_minter_transfer();
+    }
+
+    /// Accept the thCSPR minter role proposed to this contract (admin only)
+    pub fn accept_thcspr_minter(&mut self) {
+        self.require_admin();
+        self.thcspr_token_ref().accept_minter();
+    }
+
     /// Update the thCSPR token contract address (admin only)
     ///
     /// This is useful for:
     /// - Initial setup when contracts have circular dependencies
     /// - Migrating to a new token contract
     ///
+    /// Takes effect immediately while no thCSPR has been minted, so the
+    /// token can be wired in right after deployment. After that it goes
+    /// through the timelock.
+    ///
     /// # Arguments
     /// * `thcspr_token` - New thCSPR token contract address
     pub fn set_thcspr_token(&mut self, thcspr_token: Address) {
         self.require_admin();
-        self.thcspr_token.set(thcspr_token);
+        let call = TimelockedCall::SetThcsprToken { thcspr_token };
+        if self.total_thcspr_supply.get_or_default() == U512::zero() {
+            self.apply_call(call);
+        } else {
+            self.schedule(call);
+        }
+    }
+
+    /// Set the timelock delay for sensitive parameter changes (admin only)
+    ///
+    /// The change itself waits for the current delay. Zero makes every
+    /// timelocked setter (fees, fee routing, risk parameters, validator
+    /// migration, treasury and token) take effect immediately.
+    pub fn set_timelock_delay(&mut self, delay_ms: u64) {
+        self.require_admin();
+        if delay_ms > MAX_TIMELOCK_DELAY_MS {
+            self.env().revert(Error::InvalidTimelockDelay);
+        }
+        self.schedule(TimelockedCall::SetTimelockDelay { delay_ms });
+    }
+
+    /// Execute a queued parameter change once its ETA has passed
+    ///
+    /// Permissionless: the change was authorized when it was queued.
+    pub fn execute_operation(&mut self, id: u64) {
+        let operation = self
+            .timelock
+            .get_operation(id)
+            .unwrap_or_revert_with(&self.env(), Error::OperationNotQueued);
+        if self.env().get_block_time() < operation.eta {
+            self.env().revert(Error::TimelockNotReady);
+        }
+        self.timelock.dequeue(id);
+        self.env().emit_event(OperationExecuted { id });
+        self.apply_call(operation.call);
+    }
+
+    /// Cancel a queued parameter change (same permission as queuing it)
+    pub fn cancel_operation(&mut self, id: u64) {
+        let operation = self
+            .timelock
+            .get_operation(id)
+            .unwrap_or_revert_with(&self.env(), Error::OperationNotQueued);
+        match operation.call {
+            TimelockedCall::SetProtocolFee { .. }
+            | TimelockedCall::SetTreasury { .. }
+            | TimelockedCall::SetFeeMode { .. }
+            | TimelockedCall::SetFeeRecipients { .. }
+            | TimelockedCall::SetKeeperRewardConfig { .. } => self.require_role(FEE_MANAGER_ROLE),
+            TimelockedCall::SetMinStake { .. }
+            | TimelockedCall::SetStakeCaps { .. }
+            | TimelockedCall::SetCapExempt { .. }
+            | TimelockedCall::SetDelegationThreshold { .. }
+            | TimelockedCall::SetUnbondingDelay { .. }
+            | TimelockedCall::SetLiquidityBufferConfig { .. } => {
+                self.require_role(RISK_MANAGER_ROLE)
+            }
+            TimelockedCall::SetValidator { .. }
+            | TimelockedCall::StartValidatorMigration { .. }
+            | TimelockedCall::AddValidator { .. }
+            | TimelockedCall::RemoveValidator { .. }
+            | TimelockedCall::SetValidatorWeight { .. } => self.require_role(VALIDATOR_MANAGER_ROLE),
+            _ => self.require_admin(),
+        }
+        self.timelock.dequeue(id);
+        self.env().emit_event(OperationCancelled { id });
+    }
+
+    /// Grant one of the protocol roles to `account` (admin only)
+    pub fn grant_role(&mut self, role: Role, account: Address) {
+        self.require_admin();
+        if !is_known_role(&role) {
+            self.env().revert(Error::InvalidRole);
+        }
+        self.roles.grant(role, account);
+    }
+
+    /// Revoke a role from `account` (admin only)
+    pub fn revoke_role(&mut self, role: Role, account: Address) {
+        self.require_admin();
+        self.roles.revoke(role, account);
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: Role) {
+        let caller = self.env().caller();
+        self.roles.revoke(role, caller);
+    }
+
+    /// Restrict `compound` to keepers, or open it to anyone (admin only)
+    pub fn set_keeper_only_compound(&mut self, enabled: bool) {
+        self.require_admin();
+        self.keeper_only_compound.set(enabled);
+    }
+}
+
+/// Split `amount` proportionally to `weights`, never exceeding any weight
+///
+/// Returns one share per weight. The shares can sum to less than `amount`
+/// because of rounding, or when the weights sum to less than `amount`.
+fn split_proportionally(amount: U512, weights: &[U512]) -> Vec<U512> {
+    let total = weights.iter().fold(U512::zero(), |acc, w| acc + *w);
+    if total == U512::zero() {
+        return vec![U512::zero(); weights.len()];
     }
+    let to_split = amount.min(total);
+    weights.iter().map(|w| to_split * *w / total).collect()
 }
@@ -7,6 +7,7 @@ use odra::casper_types::{U256, U512};
 use odra_modules::cep18_token::Cep18;
 
 use crate::errors::Error;
+use crate::events::{MinterTransferCancelled, MinterTransferProposed, MinterTransferred};
 
 /// thCSPR - Liquid staking token representing staked CSPR
 #[odra::module]
@@ -15,6 +16,8 @@ pub struct ThCsprToken {
     cep18: SubModule<Cep18>,
     /// Address authorized to mint/burn (ThawCore contract)
     minter: Var<Address>,
+    /// Proposed minter waiting to accept the handover
+    pending_minter: Var<Option<Address>>,
 }
 
 #[odra::module]
@@ -96,6 +99,48 @@ impl ThCsprToken {
         self.minter.get()
     }
 
+    /// Get the proposed minter waiting to accept, if any
+    pub fn get_pending_minter(&self) -> Option<Address> {
+        self.pending_minter.get().flatten()
+    }
+
+    /// Propose a new minter - only callable by the current minter
+    pub fn propose_minter(&mut self, new_minter: Address) {
+        self.require_minter();
+        self.pending_minter.set(Some(new_minter));
+        self.env().emit_event(MinterTransferProposed {
+            current_minter: self.env().caller(),
+            pending_minter: new_minter,
+        });
+    }
+
+    /// Accept a pending minter transfer - only callable by the proposed minter
+    pub fn accept_minter(&mut self) {
+        let new_minter = self
+            .get_pending_minter()
+            .unwrap_or_revert_with(&self.env(), Error::NoPendingMinter);
+        if self.env().caller() != new_minter {
+            self.env().revert(Error::NotPendingMinter);
+        }
+        let old_minter = self.minter.get().unwrap_or_revert_with(&self.env(), Error::MinterNotSet);
+        self.minter.set(new_minter);
+        self.pending_minter.set(None);
+        self.env().emit_event(MinterTransferred {
+            old_minter,
+            new_minter,
+        });
+    }
+
+    /// Cancel a pending minter transfer - only callable by the current minter
+    pub fn cancel_minter_transfer(&mut self) {
+        self.require_minter();
+        let cancelled_minter = self
+            .get_pending_minter()
+            .unwrap_or_revert_with(&self.env(), Error::NoPendingMinter);
+        self.pending_minter.set(None);
+        self.env().emit_event(MinterTransferCancelled { cancelled_minter });
+    }
+
     // Internal functions
 
     fn require_minter(&self) {
@@ -0,0 +1,174 @@
+//! Timelock - Delayed execution of sensitive parameter changes
+//!
+//! Used as a submodule by ThawCore and LendingPool. The host contract checks
+//! permissions and validates arguments, then hands the call to the timelock.
+//! With a zero delay the host applies the call straight away; otherwise the
+//! call is queued with an ETA, stays readable on chain, and can be executed
+//! once the ETA has passed or cancelled before that.
+//!
+//! Hosts start with `DEFAULT_TIMELOCK_DELAY_MS`, and changing the delay is
+//! itself timelocked.
+//!
+//! Both hosts share `TimelockedCall`; each one reverts with its
+//! `UnsupportedTimelockCall` error on a call meant for the other.
+
+use alloc::vec::Vec;
+use odra::prelude::*;
+use odra::casper_types::{PublicKey, U512};
+
+use crate::fee_splitter::FeeRecipient;
+use crate::thaw_core::FeeMode;
+
+/// Longest delay the timelock accepts (30 days)
+pub const MAX_TIMELOCK_DELAY_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Delay both hosts start with (48 hours)
+pub const DEFAULT_TIMELOCK_DELAY_MS: u64 = 48 * 60 * 60 * 1000;
+
+/// A parameter change that must wait for the timelock delay
+#[odra::odra_type]
+pub enum TimelockedCall {
+    /// ThawCore: change the protocol fee on rewards
+    SetProtocolFee { fee_bps: u64 },
+    /// ThawCore: migrate the primary validator
+    SetValidator { validator: PublicKey },
+    /// ThawCore: move all delegation from one validator to another
+    StartValidatorMigration { from: PublicKey, to: PublicKey },
+    /// ThawCore: add a validator to the delegation set
+    AddValidator { validator: PublicKey, weight: u64 },
+    /// ThawCore: remove an undelegated validator from the set
+    RemoveValidator { validator: PublicKey },
+    /// ThawCore: change the target weight of a validator
+    SetValidatorWeight { validator: PublicKey, weight: u64 },
+    /// ThawCore: point to a new thCSPR token contract
+    SetThcsprToken { thcspr_token: Address },
+    /// ThawCore: change the treasury address
+    SetTreasury { treasury: Address },
+    /// ThawCore: change how the protocol fee is paid
+    SetFeeMode { mode: FeeMode },
+    /// ThawCore: change the compound keeper reward
+    SetKeeperRewardConfig {
+        reward_bps: u64,
+        max_reward: U512,
+        min_interval_ms: u64,
+    },
+    /// ThawCore: change the minimum stake
+    SetMinStake { min_stake: U512 },
+    /// ThawCore: change the global and per-address stake caps
+    SetStakeCaps {
+        global_cap: U512,
+        per_address_cap: U512,
+    },
+    /// ThawCore: exempt an address from the per-address cap, or revoke it
+    SetCapExempt { account: Address, exempt: bool },
+    /// ThawCore: change the batch delegation threshold
+    SetDelegationThreshold { threshold: U512 },
+    /// ThawCore: change the unbonding delay of new withdrawal requests
+    SetUnbondingDelay { delay_ms: u64 },
+    /// ThawCore: change the liquidity buffer and instant unstake fee curve
+    SetLiquidityBufferConfig {
+        target_bps: u64,
+        stake_share_bps: u64,
+        min_fee_bps: u64,
+        max_fee_bps: u64,
+    },
+    /// LendingPool: change collateral factor, liquidation threshold and bonus
+    SetLendingConfig {
+        collateral_factor: u64,
+        liq_threshold: u64,
+        liq_bonus: u64,
+    },
+    /// LendingPool: change the kinked interest rate model
+    SetInterestRateModel {
+        base_rate_bps: u64,
+        slope1_bps: u64,
+        optimal_utilization_bps: u64,
+        slope2_bps: u64,
+    },
+    /// LendingPool: change the share of interest kept as reserves
+    SetReserveFactor { reserve_factor_bps: u64 },
+    /// LendingPool: change the staking APR used by `preview_leverage`
+    SetStakingApr { apr_bps: u64 },
+    /// LendingPool: send protocol reserves to an address
+    WithdrawReserves { to: Address, amount: U512 },
+    /// Either contract: change the fee recipients and their shares
+    SetFeeRecipients { recipients: Vec<FeeRecipient> },
+    /// Either contract: change the timelock delay itself
+    SetTimelockDelay { delay_ms: u64 },
+}
+
+/// A queued call and the earliest time it can be executed
+#[odra::odra_type]
+pub struct QueuedOperation {
+    pub id: u64,
+    pub call: TimelockedCall,
+    /// Block time (ms) from which the call can be executed
+    pub eta: u64,
+}
+
+/// Queue of delayed calls for a host contract
+#[odra::module]
+pub struct Timelock {
+    delay_ms: Var<u64>,
+    operation_counter: Var<u64>,
+    operations: Mapping<u64, Option<QueuedOperation>>,
+    queued_ids: Var<Vec<u64>>,
+}
+
+#[odra::module]
+impl Timelock {
+    /// Get the delay applied to newly queued calls
+    pub fn get_delay(&self) -> u64 {
+        self.delay_ms.get_or_default()
+    }
+
+    /// Get a queued operation by id
+    pub fn get_operation(&self, id: u64) -> Option<QueuedOperation> {
+        self.operations.get(&id).flatten()
+    }
+
+    /// Get all operations still waiting to be executed or cancelled
+    pub fn get_queued_operations(&self) -> Vec<QueuedOperation> {
+        self.queued_ids
+            .get_or_default()
+            .iter()
+            .filter_map(|id| self.get_operation(*id))
+            .collect()
+    }
+}
+
+impl Timelock {
+    /// Set the delay (the host checks it against `MAX_TIMELOCK_DELAY_MS`)
+    pub fn set_delay(&mut self, delay_ms: u64) {
+        self.delay_ms.set(delay_ms);
+    }
+
+    /// Queue `call` to be executable `get_delay()` after `now`
+    pub fn queue(&mut self, call: TimelockedCall, now: u64) -> QueuedOperation {
+        let id = self.operation_counter.get_or_default();
+        self.operation_counter.set(id + 1);
+
+        let operation = QueuedOperation {
+            id,
+            call,
+            eta: now + self.get_delay(),
+        };
+        self.operations.set(&id, Some(operation.clone()));
+
+        let mut ids = self.queued_ids.get_or_default();
+        ids.push(id);
+        self.queued_ids.set(ids);
+
+        operation
+    }
+
+    /// Remove a queued operation, returning it if it was queued
+    pub fn dequeue(&mut self, id: u64) -> Option<QueuedOperation> {
+        let operation = self.get_operation(id)?;
+        self.operations.set(&id, None);
+        let mut ids = self.queued_ids.get_or_default();
+        ids.retain(|queued| *queued != id);
+        self.queued_ids.set(ids);
+        Some(operation)
+    }
+}
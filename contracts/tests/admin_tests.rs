@@ -9,7 +9,7 @@ use odra::prelude::*;
 use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
 use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
 use thaw::errors::Error;
-use thaw::events::{AdminTransferred, FeeUpdated, Paused, Unpaused};
+use thaw::events::{AdminTransferred, FeeUpdated, Paused, UnbondingDelayUpdated, Unpaused};
 
 use test_utils::*;
 
@@ -23,16 +23,12 @@ fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address) {
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder, will update after token deploy
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -49,6 +45,7 @@ fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address) {
     // Update ThawCore to use the correct token address
     env.set_caller(admin);
     thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
 
     (env, thaw_core, thcspr_token, admin, user)
 }
@@ -92,8 +89,8 @@ fn test_non_admin_pause() {
     assert!(result.is_err(), "Non-admin should not be able to pause");
     assert_eq!(
         result.unwrap_err(),
-        Error::NotAdmin.into(),
-        "Should revert with NotAdmin error"
+        Error::MissingRole.into(),
+        "Should revert with MissingRole error"
     );
 }
 
@@ -110,7 +107,7 @@ fn test_non_admin_unpause() {
     env.set_caller(user);
     let result = thaw_core.try_unpause();
     assert!(result.is_err(), "Non-admin should not be able to unpause");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 }
 
 #[test]
@@ -166,19 +163,22 @@ fn test_non_admin_set_fee() {
 
     let result = thaw_core.try_set_protocol_fee(500);
     assert!(result.is_err(), "Non-admin should not be able to set fee");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 }
 
 #[test]
 fn test_transfer_admin() {
-    // Transfer admin → old admin can't call → new admin can
+    // Propose → accept → old admin can't call → new admin can
     let (env, mut thaw_core, _thcspr_token, admin, _user) = setup();
 
     let new_admin = env.get_account(5);
 
-    // Current admin transfers to new admin
+    // Current admin proposes the new admin, who accepts
     env.set_caller(admin);
-    thaw_core.transfer_admin(new_admin);
+    thaw_core.propose_admin(new_admin);
+    assert_eq!(thaw_core.get_admin(), Some(admin));
+    env.set_caller(new_admin);
+    thaw_core.accept_admin();
 
     // Verify new admin
     assert_eq!(thaw_core.get_admin(), Some(new_admin));
@@ -187,7 +187,7 @@ fn test_transfer_admin() {
     env.set_caller(admin);
     let result = thaw_core.try_pause();
     assert!(result.is_err(), "Old admin should not be able to pause");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 
     // New admin can pause
     env.set_caller(new_admin);
@@ -202,7 +202,7 @@ fn test_non_admin_transfer_admin() {
     env.set_caller(user);
 
     let new_admin = env.get_account(5);
-    let result = thaw_core.try_transfer_admin(new_admin);
+    let result = thaw_core.try_propose_admin(new_admin);
     assert!(result.is_err(), "Non-admin should not be able to transfer admin");
     assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
 }
@@ -237,7 +237,7 @@ fn test_non_admin_set_min_stake() {
 
     let result = thaw_core.try_set_min_stake(U512::from(100u64 * CSPR));
     assert!(result.is_err(), "Non-admin should not be able to set min stake");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 }
 
 #[test]
@@ -260,7 +260,7 @@ fn test_non_admin_set_treasury() {
     let new_treasury = env.get_account(6);
     let result = thaw_core.try_set_treasury(new_treasury);
     assert!(result.is_err(), "Non-admin should not be able to set treasury");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 }
 
 #[test]
@@ -283,30 +283,7 @@ fn test_non_admin_set_validator() {
     let new_validator = create_mock_validator_key();
     let result = thaw_core.try_set_validator(new_validator);
     assert!(result.is_err(), "Non-admin should not be able to set validator");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
-}
-
-#[test]
-fn test_set_auction() {
-    let (env, mut thaw_core, _thcspr_token, admin, _user) = setup();
-
-    env.set_caller(admin);
-
-    let new_auction = env.get_account(8);
-    thaw_core.set_auction(new_auction);
-    assert_eq!(thaw_core.get_auction(), Some(new_auction));
-}
-
-#[test]
-fn test_non_admin_set_auction() {
-    let (env, mut thaw_core, _thcspr_token, _admin, user) = setup();
-
-    env.set_caller(user);
-
-    let new_auction = env.get_account(8);
-    let result = thaw_core.try_set_auction(new_auction);
-    assert!(result.is_err(), "Non-admin should not be able to set auction");
-    assert_eq!(result.unwrap_err(), Error::NotAdmin.into());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
 }
 
 #[test]
@@ -365,7 +342,9 @@ fn test_transfer_admin_emits_event() {
     env.set_caller(admin);
 
     let new_admin = env.get_account(5);
-    thaw_core.transfer_admin(new_admin);
+    thaw_core.propose_admin(new_admin);
+    env.set_caller(new_admin);
+    thaw_core.accept_admin();
 
     let expected_event = AdminTransferred {
         old_admin: admin,
@@ -423,3 +402,55 @@ fn test_initial_paused_state() {
 
     assert!(!thaw_core.is_paused(), "Contract should not be paused initially");
 }
+
+#[test]
+fn test_set_unbonding_delay() {
+    let (env, mut thaw_core, _thcspr_token, admin, user) = setup();
+
+    assert_eq!(thaw_core.get_unbonding_delay(), UNBONDING_PERIOD_MS);
+
+    // Testnet-style shorter delay
+    let new_delay = 2 * 60 * 60 * 1000u64;
+    env.set_caller(admin);
+    thaw_core.set_unbonding_delay(new_delay);
+    assert_eq!(thaw_core.get_unbonding_delay(), new_delay);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        UnbondingDelayUpdated {
+            old_delay_ms: UNBONDING_PERIOD_MS,
+            new_delay_ms: new_delay,
+        }
+    ));
+
+    // New withdrawals use the new delay
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+    let withdrawal_id = thaw_core.unstake(U512::from(50u64 * CSPR));
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+    assert_eq!(
+        withdrawal.claimable_timestamp,
+        withdrawal.request_timestamp + new_delay
+    );
+}
+
+#[test]
+fn test_set_unbonding_delay_invalid() {
+    let (env, mut thaw_core, _thcspr_token, admin, _user) = setup();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_set_unbonding_delay(0);
+    assert_eq!(result.unwrap_err(), Error::InvalidUnbondingDelay.into());
+
+    let result = thaw_core.try_set_unbonding_delay(31 * 24 * 60 * 60 * 1000);
+    assert_eq!(result.unwrap_err(), Error::InvalidUnbondingDelay.into());
+}
+
+#[test]
+fn test_set_unbonding_delay_not_admin() {
+    let (env, mut thaw_core, _thcspr_token, _admin, user) = setup();
+
+    env.set_caller(user);
+    let result = thaw_core.try_set_unbonding_delay(UNBONDING_PERIOD_MS);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
@@ -0,0 +1,270 @@
+//! Two-step admin and minter handover tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::{Error, LendingError};
+use thaw::events::{
+    AdminTransferCancelled, AdminTransferProposed, AdminTransferred, MinterTransferCancelled,
+    MinterTransferProposed, MinterTransferred,
+};
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to deploy ThawCore
+fn deploy_core(env: &HostEnv, admin: Address) -> ThawCoreHostRef {
+    ThawCore::deploy(
+        env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury: env.get_account(1),
+            admin,
+        },
+    )
+}
+
+/// Helper to setup linked ThawCore, thCSPR token and LendingPool
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, LendingPoolHostRef, Address) {
+    let env = odra_test::env();
+    let admin = env.get_account(0);
+
+    let mut thaw_core = deploy_core(&env, admin);
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+
+    (env, thaw_core, thcspr_token, lending_pool, admin)
+}
+
+#[test]
+fn test_propose_admin_keeps_current_admin() {
+    let (env, mut thaw_core, _thcspr_token, _lending_pool, admin) = setup();
+    let new_admin = env.get_account(5);
+
+    env.set_caller(admin);
+    thaw_core.propose_admin(new_admin);
+
+    assert_eq!(thaw_core.get_admin(), Some(admin));
+    assert_eq!(thaw_core.get_pending_admin(), Some(new_admin));
+    assert!(env.emitted_event(
+        &thaw_core,
+        AdminTransferProposed {
+            current_admin: admin,
+            pending_admin: new_admin,
+        }
+    ));
+
+    // Admin functions stay with the current admin until acceptance
+    thaw_core.pause();
+    env.set_caller(new_admin);
+    assert_eq!(thaw_core.try_unpause(), Err(Error::MissingRole.into()));
+}
+
+#[test]
+fn test_accept_admin_only_by_pending_admin() {
+    let (env, mut thaw_core, _thcspr_token, _lending_pool, admin) = setup();
+    let new_admin = env.get_account(5);
+    let other = env.get_account(6);
+
+    env.set_caller(admin);
+    assert_eq!(thaw_core.try_accept_admin(), Err(Error::NoPendingAdmin.into()));
+    thaw_core.propose_admin(new_admin);
+
+    env.set_caller(other);
+    assert_eq!(thaw_core.try_accept_admin(), Err(Error::NotPendingAdmin.into()));
+
+    env.set_caller(new_admin);
+    thaw_core.accept_admin();
+    assert_eq!(thaw_core.get_admin(), Some(new_admin));
+    assert_eq!(thaw_core.get_pending_admin(), None);
+    assert!(env.emitted_event(
+        &thaw_core,
+        AdminTransferred {
+            old_admin: admin,
+            new_admin,
+        }
+    ));
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let (env, mut thaw_core, _thcspr_token, _lending_pool, admin) = setup();
+    let new_admin = env.get_account(5);
+
+    env.set_caller(admin);
+    assert_eq!(thaw_core.try_cancel_admin_transfer(), Err(Error::NoPendingAdmin.into()));
+    thaw_core.propose_admin(new_admin);
+
+    env.set_caller(new_admin);
+    assert_eq!(thaw_core.try_cancel_admin_transfer(), Err(Error::NotAdmin.into()));
+
+    env.set_caller(admin);
+    thaw_core.cancel_admin_transfer();
+    assert_eq!(thaw_core.get_pending_admin(), None);
+    assert!(env.emitted_event(
+        &thaw_core,
+        AdminTransferCancelled {
+            cancelled_admin: new_admin,
+        }
+    ));
+
+    env.set_caller(new_admin);
+    assert_eq!(thaw_core.try_accept_admin(), Err(Error::NoPendingAdmin.into()));
+}
+
+#[test]
+fn test_lending_pool_admin_transfer() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, admin) = setup();
+    let new_admin = env.get_account(5);
+
+    env.set_caller(new_admin);
+    assert_eq!(
+        lending_pool.try_propose_admin(new_admin),
+        Err(LendingError::NotAdmin.into())
+    );
+
+    env.set_caller(admin);
+    lending_pool.propose_admin(new_admin);
+    assert_eq!(lending_pool.get_admin(), Some(admin));
+    assert_eq!(lending_pool.get_pending_admin(), Some(new_admin));
+
+    env.set_caller(env.get_account(6));
+    assert_eq!(
+        lending_pool.try_accept_admin(),
+        Err(LendingError::NotPendingAdmin.into())
+    );
+
+    env.set_caller(new_admin);
+    lending_pool.accept_admin();
+    assert_eq!(lending_pool.get_admin(), Some(new_admin));
+    assert!(env.emitted_event(
+        &lending_pool,
+        AdminTransferred {
+            old_admin: admin,
+            new_admin,
+        }
+    ));
+
+    // Old admin lost access, new admin has it
+    env.set_caller(admin);
+    assert_eq!(
+        lending_pool.try_set_config(7000, 8000, 500),
+        Err(LendingError::MissingRole.into())
+    );
+    env.set_caller(new_admin);
+    lending_pool.set_config(7000, 8000, 500);
+}
+
+#[test]
+fn test_lending_pool_cancel_admin_transfer() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, admin) = setup();
+    let new_admin = env.get_account(5);
+
+    env.set_caller(admin);
+    lending_pool.propose_admin(new_admin);
+    lending_pool.cancel_admin_transfer();
+    assert_eq!(lending_pool.get_pending_admin(), None);
+
+    env.set_caller(new_admin);
+    assert_eq!(
+        lending_pool.try_accept_admin(),
+        Err(LendingError::NoPendingAdmin.into())
+    );
+}
+
+#[test]
+fn test_minter_handover_to_new_core() {
+    // Old ThawCore proposes a replacement ThawCore, which accepts the minter role
+    let (env, mut thaw_core, thcspr_token, _lending_pool, admin) = setup();
+    let mut new_core = deploy_core(&env, admin);
+    env.set_caller(admin);
+    new_core.set_thcspr_token(thcspr_token.address());
+
+    let user = env.get_account(2);
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.propose_thcspr_minter(new_core.address());
+    assert_eq!(thcspr_token.get_minter(), Some(thaw_core.address()));
+    assert_eq!(thcspr_token.get_pending_minter(), Some(new_core.address()));
+    assert!(env.emitted_event(
+        &thcspr_token,
+        MinterTransferProposed {
+            current_minter: thaw_core.address(),
+            pending_minter: new_core.address(),
+        }
+    ));
+
+    new_core.accept_thcspr_minter();
+    assert_eq!(thcspr_token.get_minter(), Some(new_core.address()));
+    assert_eq!(thcspr_token.get_pending_minter(), None);
+    assert!(env.emitted_event(
+        &thcspr_token,
+        MinterTransferred {
+            old_minter: thaw_core.address(),
+            new_minter: new_core.address(),
+        }
+    ));
+
+    // Only the new core can mint now
+    env.set_caller(user);
+    let result = thaw_core.with_tokens(U512::from(100u64 * CSPR)).try_stake();
+    assert_eq!(result, Err(Error::NotMinter.into()));
+    new_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+}
+
+#[test]
+fn test_minter_handover_checks() {
+    let (env, mut thaw_core, mut thcspr_token, _lending_pool, admin) = setup();
+    let new_minter = env.get_account(5);
+
+    // Only the minter can propose, only ThawCore's admin can make it do so
+    env.set_caller(new_minter);
+    assert_eq!(thcspr_token.try_propose_minter(new_minter), Err(Error::NotMinter.into()));
+    assert_eq!(thaw_core.try_propose_thcspr_minter(new_minter), Err(Error::NotAdmin.into()));
+    assert_eq!(thcspr_token.try_accept_minter(), Err(Error::NoPendingMinter.into()));
+
+    env.set_caller(admin);
+    thaw_core.propose_thcspr_minter(new_minter);
+
+    env.set_caller(env.get_account(6));
+    assert_eq!(thcspr_token.try_accept_minter(), Err(Error::NotPendingMinter.into()));
+
+    env.set_caller(admin);
+    thaw_core.cancel_thcspr_minter_transfer();
+    assert_eq!(thcspr_token.get_pending_minter(), None);
+    assert!(env.emitted_event(
+        &thcspr_token,
+        MinterTransferCancelled {
+            cancelled_minter: new_minter,
+        }
+    ));
+
+    env.set_caller(new_minter);
+    assert_eq!(thcspr_token.try_accept_minter(), Err(Error::NoPendingMinter.into()));
+    assert_eq!(thcspr_token.get_minter(), Some(thaw_core.address()));
+}
@@ -23,16 +23,12 @@ fn setup_with_withdrawal() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Add
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder, will update after token deploy
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -49,6 +45,7 @@ fn setup_with_withdrawal() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Add
     // Update ThawCore to use the correct token address
     env.set_caller(admin);
     thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
 
     // User stakes and unstakes
     env.set_caller(user);
@@ -87,7 +84,7 @@ fn test_claim_after_unbonding() {
     env.set_caller(user);
 
     // Advance time past unbonding period
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // Claim should succeed
     let result = thaw_core.try_claim(withdrawal_id);
@@ -106,7 +103,7 @@ fn test_claim_not_owner() {
     let other_user = env.get_account(5);
 
     // Advance time past unbonding period
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // Other user tries to claim
     env.set_caller(other_user);
@@ -133,7 +130,7 @@ fn test_double_claim() {
     env.set_caller(user);
 
     // Advance time past unbonding period
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // First claim should succeed
     let result1 = thaw_core.try_claim(withdrawal_id);
@@ -179,7 +176,7 @@ fn test_claim_returns_correct_amount() {
     let expected_amount = withdrawal.cspr_amount;
 
     // Advance time and claim
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
     let claimed_amount = thaw_core.claim(withdrawal_id);
 
     assert_eq!(claimed_amount, expected_amount, "Claimed amount should match withdrawal amount");
@@ -196,7 +193,7 @@ fn test_claim_emits_event() {
     let expected_amount = withdrawal.cspr_amount;
 
     // Advance time and claim
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
     thaw_core.claim(withdrawal_id);
 
     // Verify Claimed event
@@ -220,7 +217,7 @@ fn test_claim_exactly_at_unbonding_end() {
     env.set_caller(user);
 
     // Advance time to exactly the unbonding end
-    env.advance_block_time(UNBONDING_PERIOD_MS);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS);
 
     // Should succeed exactly at unbonding end
     let result = thaw_core.try_claim(withdrawal_id);
@@ -235,7 +232,7 @@ fn test_claim_one_ms_before_unbonding() {
     env.set_caller(user);
 
     // Advance time to 1ms before unbonding ends
-    env.advance_block_time(UNBONDING_PERIOD_MS - 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS - 1);
 
     // Should fail 1ms before
     let result = thaw_core.try_claim(withdrawal_id);
@@ -254,7 +251,7 @@ fn test_claim_multiple_withdrawals() {
     let withdrawal_id2 = thaw_core.unstake(unstake_amount);
 
     // Advance time past unbonding
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // Claim first withdrawal
     let result1 = thaw_core.try_claim(withdrawal_id1);
@@ -277,7 +274,7 @@ fn test_claim_works_when_paused() {
     let (env, mut thaw_core, _thcspr_token, admin, _treasury, user, withdrawal_id) = setup_with_withdrawal();
 
     // Advance time past unbonding
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // Admin pauses contract
     env.set_caller(admin);
@@ -298,9 +295,51 @@ fn test_claim_long_after_unbonding() {
 
     // Advance time by 2 weeks
     let two_weeks_ms = 14 * 24 * 60 * 60 * 1000u64;
-    env.advance_block_time(UNBONDING_PERIOD_MS + two_weeks_ms);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + two_weeks_ms);
 
     // Should still be able to claim
     let result = thaw_core.try_claim(withdrawal_id);
     assert!(result.is_ok(), "Claim should work long after unbonding");
 }
+
+#[test]
+fn test_claim_waits_for_unbonded_funds() {
+    // Delay elapsed but the auction has not returned the CSPR yet → revert
+    let (env, mut thaw_core, _thcspr_token, admin, _treasury, user, _withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(admin);
+    thaw_core.set_unbonding_delay(1000);
+
+    env.set_caller(user);
+    let withdrawal_id = thaw_core.unstake(U512::from(10u64 * CSPR));
+    env.advance_block_time(1001);
+
+    let result = thaw_core.try_claim(withdrawal_id);
+    assert_eq!(result.unwrap_err(), Error::FundsNotYetUnbonded.into());
+
+    // Once the auction pays out, the claim goes through
+    env.advance_with_auctions(UNBONDING_PERIOD_MS);
+    assert!(thaw_core.try_claim(withdrawal_id).is_ok());
+}
+
+#[test]
+fn test_unbonding_and_claimable_liquidity() {
+    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user, withdrawal_id) = setup_with_withdrawal();
+
+    let amount = thaw_core.get_withdrawal(withdrawal_id).unwrap().cspr_amount;
+
+    // Still unbonding: nothing claimable yet
+    assert_eq!(thaw_core.get_total_unbonding(), amount);
+    assert_eq!(thaw_core.get_claimable_liquidity(), U512::zero());
+
+    // Back in the purse: fully claimable
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    assert_eq!(thaw_core.get_total_unbonding(), U512::zero());
+    assert_eq!(thaw_core.get_claimable_liquidity(), amount);
+
+    // Claimed: no liabilities left
+    env.set_caller(user);
+    thaw_core.claim(withdrawal_id);
+    assert_eq!(thaw_core.get_total_unbonding(), U512::zero());
+    assert_eq!(thaw_core.get_claimable_liquidity(), U512::zero());
+}
@@ -11,7 +11,9 @@ use odra::casper_types::U512;
 use odra::host::{Deployer, HostEnv, HostRef};
 use odra::prelude::*;
 
-use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::errors::Error;
+use thaw::events::{Compounded, FeeModeUpdated, KeeperRewardConfigUpdated};
+use thaw::thaw_core::{FeeMode, ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
 use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
 
 use test_utils::*;
@@ -26,16 +28,12 @@ fn setup_with_stake() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address,
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder, will update after token deploy
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -52,6 +50,7 @@ fn setup_with_stake() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address,
     // Update ThawCore to use the correct token address
     env.set_caller(admin);
     thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
 
     // User stakes
     env.set_caller(user);
@@ -127,16 +126,12 @@ fn test_exchange_rate_initial() {
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -153,6 +148,7 @@ fn test_exchange_rate_initial() {
     // Update ThawCore to use the correct token address
     env.set_caller(admin);
     thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
 
     // Exchange rate should be 1:1 on empty pool
     let exchange_rate = thaw_core.get_exchange_rate();
@@ -266,16 +262,12 @@ fn test_compound_empty_pool() {
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -292,6 +284,7 @@ fn test_compound_empty_pool() {
     // Update ThawCore to use the correct token address
     env.set_caller(admin);
     thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
 
     // Compound on empty pool should not fail
     let result = thaw_core.try_compound();
@@ -317,3 +310,288 @@ fn test_total_pooled_and_supply_consistency() {
     assert_eq!(pooled_before, pooled_after);
     assert_eq!(supply_before, supply_after);
 }
+
+const ERA_MS: u64 = 41_000;
+const KEEPER_REWARD_BPS: u64 = 500;
+const MIN_INTERVAL_MS: u64 = 2 * ERA_MS;
+
+/// Helper to setup a pool with a liquidity buffer, keeper reward and accrued rewards
+fn setup_with_rewards() -> (HostEnv, ThawCoreHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    // Keep 10% of stakes liquid so fees and keeper rewards can be paid out
+    thaw_core.set_liquidity_buffer_config(1000, 1000, 10, 300);
+    thaw_core.set_keeper_reward_config(
+        KEEPER_REWARD_BPS,
+        U512::from(10u64 * CSPR),
+        MIN_INTERVAL_MS,
+    );
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    // Let the delegation earn rewards for a few eras
+    env.advance_with_auctions(3 * ERA_MS);
+
+    (env, thaw_core, admin, treasury)
+}
+
+#[test]
+fn test_compound_pays_keeper() {
+    let (env, mut thaw_core, _admin, treasury) = setup_with_rewards();
+    let keeper = env.get_account(7);
+
+    let info = thaw_core.get_compound_info();
+    assert!(info.pending_rewards > U512::zero(), "Delegation should have earned rewards");
+    let expected_reward =
+        info.pending_rewards * U512::from(KEEPER_REWARD_BPS) / U512::from(10000u64);
+    assert_eq!(info.expected_keeper_reward, expected_reward);
+
+    let keeper_balance = env.balance_of(&keeper);
+    let treasury_balance = env.balance_of(&treasury);
+    let pooled_before = thaw_core.get_total_pooled();
+
+    env.set_caller(keeper);
+    let rewards_to_pool = thaw_core.compound();
+
+    let protocol_fee = info.pending_rewards / U512::from(10u64);
+    assert_eq!(env.balance_of(&keeper), keeper_balance + expected_reward);
+    assert_eq!(env.balance_of(&treasury), treasury_balance + protocol_fee);
+    assert_eq!(
+        rewards_to_pool,
+        info.pending_rewards - protocol_fee - expected_reward
+    );
+    assert_eq!(thaw_core.get_total_pooled(), pooled_before + rewards_to_pool);
+
+    // Everything harvested is now accounted for
+    assert_eq!(thaw_core.get_compound_info().pending_rewards, U512::zero());
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        Compounded {
+            rewards_harvested: info.pending_rewards,
+            protocol_fee,
+            rewards_to_pool,
+            new_exchange_rate: thaw_core.get_exchange_rate(),
+            keeper,
+            keeper_reward: expected_reward,
+            fee_mode: FeeMode::Cspr,
+        }
+    ));
+}
+
+#[test]
+fn test_keeper_reward_is_capped() {
+    let (env, mut thaw_core, admin, _treasury) = setup_with_rewards();
+    let keeper = env.get_account(7);
+
+    env.set_caller(admin);
+    thaw_core.set_keeper_reward_config(KEEPER_REWARD_BPS, U512::from(1000u64), 0);
+    assert_eq!(
+        thaw_core.get_compound_info().expected_keeper_reward,
+        U512::from(1000u64)
+    );
+
+    let keeper_balance = env.balance_of(&keeper);
+    env.set_caller(keeper);
+    thaw_core.compound();
+    assert_eq!(env.balance_of(&keeper), keeper_balance + U512::from(1000u64));
+}
+
+#[test]
+fn test_compound_min_interval() {
+    let (env, mut thaw_core, _admin, _treasury) = setup_with_rewards();
+
+    let info = thaw_core.get_compound_info();
+    assert_eq!(info.last_compound_timestamp, 0);
+    assert_eq!(info.next_compound_timestamp, 0);
+
+    thaw_core.compound();
+    let now = env.block_time();
+
+    let info = thaw_core.get_compound_info();
+    assert_eq!(info.last_compound_timestamp, now);
+    assert_eq!(info.next_compound_timestamp, now + MIN_INTERVAL_MS);
+
+    // Too early, even once new rewards accrue
+    env.advance_with_auctions(ERA_MS);
+    let result = thaw_core.try_compound();
+    assert_eq!(result.unwrap_err(), Error::CompoundTooSoon.into());
+
+    env.advance_with_auctions(ERA_MS);
+    assert!(thaw_core.try_compound().is_ok());
+}
+
+#[test]
+fn test_default_deployment_compounds() {
+    // Everything is delegated, so the protocol fee is paid in thCSPR instead
+    let (env, mut thaw_core, thcspr_token, admin, treasury, _user) = setup_with_stake();
+
+    env.advance_with_auctions(3 * ERA_MS);
+    let rewards = thaw_core.get_compound_info().pending_rewards;
+    assert!(rewards > U512::zero());
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::zero());
+    let pooled_before = thaw_core.get_total_pooled();
+    let treasury_cspr = env.balance_of(&treasury);
+
+    env.set_caller(admin);
+    let rewards_to_pool = thaw_core.compound();
+
+    let protocol_fee = rewards / U512::from(10u64);
+    assert_eq!(rewards_to_pool, rewards - protocol_fee);
+    assert_eq!(thaw_core.get_total_pooled(), pooled_before + rewards);
+    assert_eq!(env.balance_of(&treasury), treasury_cspr);
+    assert!(thcspr_token.balance_of(treasury) > 0.into());
+    assert_eq!(thaw_core.get_fee_accrued(treasury), protocol_fee);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        Compounded {
+            rewards_harvested: rewards,
+            protocol_fee,
+            rewards_to_pool,
+            new_exchange_rate: thaw_core.get_exchange_rate(),
+            keeper: admin,
+            keeper_reward: U512::zero(),
+            fee_mode: FeeMode::Shares,
+        }
+    ));
+}
+
+#[test]
+fn test_keeper_paid_in_shares_without_liquidity() {
+    let (env, mut thaw_core, thcspr_token, admin, _treasury, _user) = setup_with_stake();
+    let keeper = env.get_account(7);
+
+    env.set_caller(admin);
+    thaw_core.set_keeper_reward_config(KEEPER_REWARD_BPS, U512::from(10u64 * CSPR), 0);
+
+    env.advance_with_auctions(3 * ERA_MS);
+    let expected_reward = thaw_core.get_compound_info().expected_keeper_reward;
+    assert!(expected_reward > U512::zero());
+    let keeper_cspr = env.balance_of(&keeper);
+
+    env.set_caller(keeper);
+    thaw_core.compound();
+
+    assert_eq!(env.balance_of(&keeper), keeper_cspr);
+    let shares = U512::from(thcspr_token.balance_of(keeper).as_u128());
+    let shares_value = thaw_core.convert_to_assets(shares);
+    assert!(expected_reward - shares_value <= U512::one(), "Shares should be worth the reward");
+}
+
+#[test]
+fn test_set_keeper_reward_config() {
+    let (env, mut thaw_core, admin, _treasury) = setup_with_rewards();
+
+    env.set_caller(admin);
+    thaw_core.set_keeper_reward_config(100, U512::from(5u64 * CSPR), ERA_MS);
+
+    let config = thaw_core.get_keeper_reward_config();
+    assert_eq!(config.reward_bps, 100);
+    assert_eq!(config.max_reward, U512::from(5u64 * CSPR));
+    assert_eq!(config.min_interval_ms, ERA_MS);
+    assert!(env.emitted_event(
+        &thaw_core,
+        KeeperRewardConfigUpdated {
+            reward_bps: 100,
+            max_reward: U512::from(5u64 * CSPR),
+            min_interval_ms: ERA_MS,
+        }
+    ));
+
+    let result = thaw_core.try_set_keeper_reward_config(501, U512::zero(), 0);
+    assert_eq!(result.unwrap_err(), Error::InvalidKeeperConfig.into());
+
+    env.set_caller(env.get_account(7));
+    let result = thaw_core.try_set_keeper_reward_config(100, U512::zero(), 0);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
+
+#[test]
+fn test_fee_taken_as_shares() {
+    // No liquid CSPR needed: the fee stays delegated, treasury gets thCSPR
+    let (env, mut thaw_core, thcspr_token, admin, treasury, _user) = setup_with_stake();
+
+    env.set_caller(admin);
+    thaw_core.set_fee_mode(FeeMode::Shares);
+
+    env.advance_with_auctions(3 * ERA_MS);
+    let rewards = thaw_core.get_compound_info().pending_rewards;
+    let pooled_before = thaw_core.get_total_pooled();
+    let supply_before = thaw_core.get_total_supply();
+    let treasury_cspr = env.balance_of(&treasury);
+
+    let rewards_to_pool = thaw_core.compound();
+
+    let protocol_fee = rewards / U512::from(10u64);
+    assert_eq!(rewards_to_pool, rewards - protocol_fee);
+    assert_eq!(thaw_core.get_total_pooled(), pooled_before + rewards);
+    assert_eq!(env.balance_of(&treasury), treasury_cspr, "No CSPR leaves the pool");
+
+    // Minted shares are worth the fee at the new exchange rate
+    let shares = thaw_core.get_total_supply() - supply_before;
+    assert!(shares > U512::zero());
+    assert_eq!(U512::from(thcspr_token.balance_of(treasury).as_u128()), shares);
+    let shares_value = shares * thaw_core.get_exchange_rate() / U512::from(EXCHANGE_RATE_PRECISION);
+    assert!(protocol_fee - shares_value <= U512::one(), "Shares should be worth the fee");
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        Compounded {
+            rewards_harvested: rewards,
+            protocol_fee,
+            rewards_to_pool,
+            new_exchange_rate: thaw_core.get_exchange_rate(),
+            keeper: admin,
+            keeper_reward: U512::zero(),
+            fee_mode: FeeMode::Shares,
+        }
+    ));
+}
+
+#[test]
+fn test_set_fee_mode() {
+    let (env, mut thaw_core, _thcspr_token, admin, _treasury, user) = setup_with_stake();
+
+    assert_eq!(thaw_core.get_fee_mode(), FeeMode::Cspr);
+
+    env.set_caller(admin);
+    thaw_core.set_fee_mode(FeeMode::Shares);
+    assert_eq!(thaw_core.get_fee_mode(), FeeMode::Shares);
+    assert!(env.emitted_event(
+        &thaw_core,
+        FeeModeUpdated {
+            old_mode: FeeMode::Cspr,
+            new_mode: FeeMode::Shares,
+        }
+    ));
+
+    env.set_caller(user);
+    let result = thaw_core.try_set_fee_mode(FeeMode::Cspr);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
@@ -0,0 +1,172 @@
+//! Delegation batching tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::Error;
+use thaw::events::{DelegationThresholdUpdated, DelegationsFlushed};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const THRESHOLD: u64 = 500 * CSPR;
+
+/// Helper to setup test environment with a 500 CSPR delegation threshold
+fn setup() -> (HostEnv, ThawCoreHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    thaw_core.set_delegation_threshold(U512::from(THRESHOLD));
+
+    (env, thaw_core, admin, user)
+}
+
+fn delegated(thaw_core: &ThawCoreHostRef) -> U512 {
+    thaw_core
+        .get_validator_allocation(create_mock_validator_key())
+        .unwrap()
+        .delegated
+}
+
+#[test]
+fn test_default_threshold_delegates_every_stake() {
+    let (env, mut thaw_core, admin, user) = setup();
+
+    env.set_caller(admin);
+    thaw_core.set_delegation_threshold(U512::zero());
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    assert_eq!(thaw_core.get_pending_delegation(), U512::zero());
+    assert_eq!(delegated(&thaw_core), U512::from(100u64 * CSPR));
+}
+
+#[test]
+fn test_small_stakes_are_batched() {
+    let (env, thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(200u64 * CSPR)).stake();
+    thaw_core.with_tokens(U512::from(200u64 * CSPR)).stake();
+
+    assert_eq!(thaw_core.get_pending_delegation(), U512::from(400u64 * CSPR));
+    assert_eq!(delegated(&thaw_core), U512::zero());
+
+    // Buffered CSPR still counts as pooled
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(400u64 * CSPR));
+    assert_eq!(thaw_core.get_exchange_rate(), U512::from(EXCHANGE_RATE_PRECISION));
+}
+
+#[test]
+fn test_stake_crossing_threshold_flushes_batch() {
+    let (env, thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(300u64 * CSPR)).stake();
+    thaw_core.with_tokens(U512::from(300u64 * CSPR)).stake();
+
+    assert_eq!(thaw_core.get_pending_delegation(), U512::zero());
+    assert_eq!(delegated(&thaw_core), U512::from(600u64 * CSPR));
+    assert!(env.emitted_event(
+        &thaw_core,
+        DelegationsFlushed {
+            amount: U512::from(600u64 * CSPR),
+        }
+    ));
+}
+
+#[test]
+fn test_flush_below_threshold_fails() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    let result = thaw_core.try_flush_delegations();
+    assert_eq!(result.unwrap_err(), Error::BelowDelegationThreshold.into());
+}
+
+#[test]
+fn test_flush_after_threshold_lowered_is_permissionless() {
+    let (env, mut thaw_core, admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.set_delegation_threshold(U512::from(50u64 * CSPR));
+
+    env.set_caller(env.get_account(5));
+    let flushed = thaw_core.flush_delegations();
+
+    assert_eq!(flushed, U512::from(100u64 * CSPR));
+    assert_eq!(thaw_core.get_pending_delegation(), U512::zero());
+    assert_eq!(delegated(&thaw_core), U512::from(100u64 * CSPR));
+}
+
+#[test]
+fn test_unstake_uses_batched_stakes_first() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(600u64 * CSPR)).stake();
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    let withdrawal_id = thaw_core.unstake(U512::from(50u64 * CSPR));
+
+    assert_eq!(thaw_core.get_pending_delegation(), U512::from(50u64 * CSPR));
+    assert_eq!(delegated(&thaw_core), U512::from(600u64 * CSPR));
+
+    // The CSPR never left the contract, so the claim is covered
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    assert_eq!(thaw_core.claim(withdrawal_id), U512::from(50u64 * CSPR));
+}
+
+#[test]
+fn test_set_delegation_threshold() {
+    let (env, mut thaw_core, admin, user) = setup();
+
+    assert_eq!(thaw_core.get_delegation_threshold(), U512::from(THRESHOLD));
+
+    env.set_caller(admin);
+    thaw_core.set_delegation_threshold(U512::from(1000u64 * CSPR));
+    assert!(env.emitted_event(
+        &thaw_core,
+        DelegationThresholdUpdated {
+            old_threshold: U512::from(THRESHOLD),
+            new_threshold: U512::from(1000u64 * CSPR),
+        }
+    ));
+
+    env.set_caller(user);
+    let result = thaw_core.try_set_delegation_threshold(U512::zero());
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
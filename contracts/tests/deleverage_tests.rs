@@ -0,0 +1,244 @@
+//! LendingPool deleverage and position unwind tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::{PositionUnwound, UnwindQueued};
+use thaw::lending_pool::{LendingAction, LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup a linked pool with 1000 CSPR of liquidity
+///
+/// With `buffer`, ThawCore keeps half of the pool liquid for instant unstakes
+/// at a flat 1% fee, and another staker has filled it with 500 CSPR.
+///
+/// Returns (env, lending_pool, user).
+fn setup(buffer: bool) -> (HostEnv, LendingPoolHostRef, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let lender = env.get_account(2);
+    let user = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    if buffer {
+        thaw_core.set_liquidity_buffer_config(5000, 10_000, 100, 100);
+        env.set_caller(env.get_account(4));
+        thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+        env.set_caller(admin);
+    }
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    (env, lending_pool, user)
+}
+
+#[test]
+fn test_deleverage_through_buffer() {
+    let (env, mut lending_pool, user) = setup(true);
+
+    // 3x on 100 CSPR: 300 thCSPR against 200 CSPR of debt
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(30_000, U512::zero());
+
+    // Down to 2x: sell 100 thCSPR, 99 CSPR after the fee repays debt
+    let returned = lending_pool.deleverage(20_000);
+    assert_eq!(returned, U512::zero());
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::from(200u64 * CSPR), U512::from(101u64 * CSPR))
+    );
+    assert!(env.emitted_event(
+        &lending_pool,
+        PositionUnwound {
+            user,
+            thcspr_sold: U512::from(100u64 * CSPR),
+            cspr_received: U512::from(99u64 * CSPR),
+            debt_repaid: U512::from(99u64 * CSPR),
+            returned: U512::zero(),
+        }
+    ));
+
+    // 1x sells the value of the debt, the fee leaves 1% of it behind.
+    // Fees stay in ThawCore, so thCSPR is now worth a little more than 1 CSPR.
+    lending_pool.deleverage(10_000);
+    let (collateral, debt) = lending_pool.get_position(user);
+    assert!(collateral > U512::from(99u64 * CSPR));
+    assert!(collateral < U512::from(100u64 * CSPR));
+    assert!(debt > U512::from(CSPR));
+    assert!(debt < U512::from(102u64 * CSPR / 100));
+}
+
+#[test]
+fn test_close_position_through_buffer() {
+    let (env, mut lending_pool, user) = setup(true);
+
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(20_000, U512::zero());
+
+    // 200 thCSPR sold for 198 CSPR, 100 of it repays the debt
+    let balance_before = env.balance_of(&user);
+    let returned = lending_pool.close_position();
+    assert_eq!(returned, U512::from(98u64 * CSPR));
+    assert_eq!(env.balance_of(&user), balance_before + returned);
+    assert_eq!(lending_pool.get_position(user), (U512::zero(), U512::zero()));
+    assert_eq!(lending_pool.get_total_borrowed(), U512::zero());
+    assert_eq!(lending_pool.get_pending_unwind(user), None);
+}
+
+#[test]
+fn test_close_position_through_queue() {
+    let (env, mut lending_pool, user) = setup(false);
+    let keeper = env.get_account(5);
+
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(20_000, U512::zero());
+
+    // No buffer: the collateral is queued and still backs the debt
+    assert_eq!(lending_pool.close_position(), U512::zero());
+    let unwind = lending_pool.get_pending_unwind(user).unwrap();
+    assert_eq!(unwind.thcspr_amount, U512::from(200u64 * CSPR));
+    assert!(env.emitted_event(
+        &lending_pool,
+        UnwindQueued {
+            user,
+            withdrawal_id: unwind.withdrawal_id,
+            thcspr_sold: U512::from(200u64 * CSPR),
+        }
+    ));
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::zero(), U512::from(100u64 * CSPR))
+    );
+    assert!(lending_pool.get_health_factor(user) > U512::from(EXCHANGE_RATE_PRECISION));
+
+    let result = lending_pool.try_close_position();
+    assert_eq!(result, Err(LendingError::UnwindPending.into()));
+    env.set_caller(keeper);
+    assert!(lending_pool.try_settle_unwind(user).is_err());
+
+    // Anyone can settle once unbonded, the remainder goes to the user
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    let balance_before = env.balance_of(&user);
+    let returned = lending_pool.settle_unwind(user);
+    assert!(returned < U512::from(100u64 * CSPR));
+    assert!(returned > U512::from(99u64 * CSPR));
+    assert_eq!(env.balance_of(&user), balance_before + returned);
+    assert_eq!(lending_pool.get_position(user), (U512::zero(), U512::zero()));
+    assert_eq!(lending_pool.get_pending_unwind(user), None);
+
+    let result = lending_pool.try_settle_unwind(user);
+    assert_eq!(result, Err(LendingError::NoUnwindPending.into()));
+}
+
+#[test]
+fn test_deleverage_invalid_targets() {
+    let (_env, mut lending_pool, _user) = setup(true);
+
+    let result = lending_pool.try_deleverage(20_000);
+    assert_eq!(result, Err(LendingError::NothingToUnwind.into()));
+    let result = lending_pool.try_close_position();
+    assert_eq!(result, Err(LendingError::NothingToUnwind.into()));
+
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(20_000, U512::zero());
+
+    // Below 1x, or at or above the current leverage
+    let invalid = Err(LendingError::InvalidLeverage.into());
+    assert_eq!(lending_pool.try_deleverage(9_999), invalid);
+    assert_eq!(lending_pool.try_deleverage(20_000), invalid);
+    assert_eq!(lending_pool.try_deleverage(30_000), invalid);
+}
+
+#[test]
+fn test_unwind_while_withdrawals_paused() {
+    let (env, mut lending_pool, user) = setup(true);
+    let admin = env.get_account(0);
+
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(30_000, U512::zero());
+
+    env.set_caller(admin);
+    lending_pool.set_action_paused(LendingAction::Withdraw, true);
+    env.set_caller(user);
+
+    // Paying down debt stays open, taking CSPR out does not
+    assert_eq!(lending_pool.deleverage(20_000), U512::zero());
+    let result = lending_pool.try_close_position();
+    assert_eq!(result, Err(LendingError::ActionPaused.into()));
+
+    env.set_caller(admin);
+    lending_pool.set_action_paused(LendingAction::Withdraw, false);
+    env.set_caller(user);
+    assert!(lending_pool.close_position() > U512::zero());
+}
+
+#[test]
+fn test_settle_unwind_while_withdrawals_paused() {
+    let (env, mut lending_pool, user) = setup(false);
+    let admin = env.get_account(0);
+
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(20_000, U512::zero());
+    lending_pool.close_position();
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+
+    env.set_caller(admin);
+    lending_pool.set_action_paused(LendingAction::Withdraw, true);
+    let result = lending_pool.try_settle_unwind(user);
+    assert_eq!(result, Err(LendingError::ActionPaused.into()));
+    assert!(lending_pool.get_pending_unwind(user).is_some());
+
+    lending_pool.set_action_paused(LendingAction::Withdraw, false);
+    assert!(lending_pool.settle_unwind(user) > U512::zero());
+}
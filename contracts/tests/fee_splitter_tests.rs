@@ -0,0 +1,223 @@
+//! Fee splitter tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::{Error, LendingError};
+use thaw::events::FeeRecipientsUpdated;
+use thaw::fee_splitter::FeeRecipient;
+use thaw::lending_pool::{LendingPool, LendingPoolInitArgs};
+use thaw::thaw_core::{FeeMode, ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const ERA_MS: u64 = 41_000;
+
+/// Helper to setup a pool with accrued rewards and a liquidity buffer
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    // Keep 10% of stakes liquid so CSPR fees can be paid out
+    thaw_core.set_liquidity_buffer_config(1000, 1000, 10, 300);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    env.advance_with_auctions(3 * ERA_MS);
+
+    (env, thaw_core, thcspr_token, admin)
+}
+
+/// Treasury 40%, insurance 30%, node operators 20%, referral pool 10%
+fn recipients(env: &HostEnv) -> Vec<FeeRecipient> {
+    [4000u64, 3000, 2000, 1000]
+        .iter()
+        .enumerate()
+        .map(|(i, share_bps)| FeeRecipient {
+            account: env.get_account(i + 4),
+            share_bps: *share_bps,
+        })
+        .collect()
+}
+
+#[test]
+fn test_fees_go_to_treasury_without_recipients() {
+    let (env, mut thaw_core, _thcspr_token, _admin) = setup();
+    let treasury = env.get_account(1);
+
+    assert!(thaw_core.get_fee_recipients().is_empty());
+
+    let protocol_fee = thaw_core.get_compound_info().pending_rewards / U512::from(10u64);
+    let balance_before = env.balance_of(&treasury);
+    thaw_core.compound();
+
+    assert_eq!(env.balance_of(&treasury), balance_before + protocol_fee);
+    assert_eq!(thaw_core.get_fee_accrued(treasury), protocol_fee);
+}
+
+#[test]
+fn test_cspr_fee_split_between_recipients() {
+    let (env, mut thaw_core, _thcspr_token, admin) = setup();
+    let recipients = recipients(&env);
+
+    env.set_caller(admin);
+    thaw_core.set_fee_recipients(recipients.clone());
+
+    let protocol_fee = thaw_core.get_compound_info().pending_rewards / U512::from(10u64);
+    let balances: Vec<U512> = recipients.iter().map(|r| env.balance_of(&r.account)).collect();
+    thaw_core.compound();
+
+    let mut total = U512::zero();
+    for (recipient, balance_before) in recipients.iter().zip(balances) {
+        let received = env.balance_of(&recipient.account) - balance_before;
+        let expected = protocol_fee * U512::from(recipient.share_bps) / U512::from(10000u64);
+        assert!(received - expected <= U512::from(3u64), "Only rounding dust differs");
+        assert_eq!(thaw_core.get_fee_accrued(recipient.account), received);
+        total += received;
+    }
+    assert_eq!(total, protocol_fee, "The whole fee is distributed");
+}
+
+#[test]
+fn test_share_fee_split_between_recipients() {
+    let (env, mut thaw_core, thcspr_token, admin) = setup();
+    let recipients = recipients(&env);
+
+    env.set_caller(admin);
+    thaw_core.set_fee_recipients(recipients.clone());
+    thaw_core.set_fee_mode(FeeMode::Shares);
+
+    let protocol_fee = thaw_core.get_compound_info().pending_rewards / U512::from(10u64);
+    let supply_before = thaw_core.get_total_supply();
+    thaw_core.compound();
+    let minted = thaw_core.get_total_supply() - supply_before;
+
+    let mut total_shares = U512::zero();
+    let mut total_accrued = U512::zero();
+    for recipient in recipients.iter() {
+        let shares = U512::from(thcspr_token.balance_of(recipient.account).as_u128());
+        let expected = minted * U512::from(recipient.share_bps) / U512::from(10000u64);
+        let dust = shares.max(expected) - shares.min(expected);
+        assert!(dust <= U512::from(3u64), "Only rounding dust differs");
+        total_shares += shares;
+        total_accrued += thaw_core.get_fee_accrued(recipient.account);
+    }
+    assert_eq!(total_shares, minted);
+    assert_eq!(total_accrued, protocol_fee);
+}
+
+#[test]
+fn test_set_fee_recipients_emits_event() {
+    let (env, mut thaw_core, _thcspr_token, admin) = setup();
+    let recipients = recipients(&env);
+
+    env.set_caller(admin);
+    thaw_core.set_fee_recipients(recipients.clone());
+
+    assert_eq!(thaw_core.get_fee_recipients(), recipients);
+    assert!(env.emitted_event(
+        &thaw_core,
+        FeeRecipientsUpdated {
+            accounts: recipients.iter().map(|r| r.account).collect(),
+            shares_bps: vec![4000, 3000, 2000, 1000],
+        }
+    ));
+}
+
+#[test]
+fn test_invalid_fee_recipients() {
+    let (env, mut thaw_core, _thcspr_token, admin) = setup();
+    env.set_caller(admin);
+
+    // Shares do not add up to 100%
+    let mut short = recipients(&env);
+    short.pop();
+    let result = thaw_core.try_set_fee_recipients(short);
+    assert_eq!(result.unwrap_err(), Error::InvalidFeeRecipients.into());
+
+    // Duplicate account
+    let mut duplicate = recipients(&env);
+    duplicate[1].account = duplicate[0].account;
+    let result = thaw_core.try_set_fee_recipients(duplicate);
+    assert_eq!(result.unwrap_err(), Error::InvalidFeeRecipients.into());
+
+    // Zero share
+    let mut zero = recipients(&env);
+    zero[3].share_bps = 0;
+    zero[0].share_bps += 1000;
+    let result = thaw_core.try_set_fee_recipients(zero);
+    assert_eq!(result.unwrap_err(), Error::InvalidFeeRecipients.into());
+}
+
+#[test]
+fn test_set_fee_recipients_not_admin() {
+    let (env, mut thaw_core, _thcspr_token, _admin) = setup();
+
+    env.set_caller(env.get_account(2));
+    let result = thaw_core.try_set_fee_recipients(recipients(&env));
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
+
+#[test]
+fn test_lending_pool_fee_recipients() {
+    let (env, thaw_core, thcspr_token, admin) = setup();
+    let recipients = recipients(&env);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    env.set_caller(admin);
+    disable_lending_timelock(&env, &mut lending_pool);
+    lending_pool.set_fee_recipients(recipients.clone());
+    assert_eq!(lending_pool.get_fee_recipients(), recipients);
+    assert_eq!(lending_pool.get_fee_accrued(recipients[0].account), U512::zero());
+    assert!(env.emitted_event(
+        &lending_pool,
+        FeeRecipientsUpdated {
+            accounts: recipients.iter().map(|r| r.account).collect(),
+            shares_bps: vec![4000, 3000, 2000, 1000],
+        }
+    ));
+
+    let result = lending_pool.try_set_fee_recipients(vec![FeeRecipient {
+        account: admin,
+        share_bps: 5000,
+    }]);
+    assert_eq!(result.unwrap_err(), LendingError::InvalidFeeRecipients.into());
+
+    env.set_caller(env.get_account(2));
+    let result = lending_pool.try_set_fee_recipients(Vec::new());
+    assert_eq!(result.unwrap_err(), LendingError::MissingRole.into());
+}
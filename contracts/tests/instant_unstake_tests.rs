@@ -0,0 +1,209 @@
+//! Instant unstake tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::Error;
+use thaw::events::{InstantUnstaked, LiquidityBufferConfigUpdated};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup test environment with a 20% buffer target fed by 50% of stakes
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let validator = create_mock_validator_key();
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator,
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    thaw_core.set_liquidity_buffer_config(2000, 5000, 10, 300);
+
+    (env, thaw_core, thcspr_token, admin, user)
+}
+
+#[test]
+fn test_stake_feeds_buffer_up_to_target() {
+    // Stake 1000 → 50% share capped by 20% target → 200 in buffer, 800 delegated
+    let (env, thaw_core, _thcspr_token, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::from(200u64 * CSPR));
+    assert_eq!(thaw_core.get_liquidity_buffer_target(), U512::from(200u64 * CSPR));
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(1000u64 * CSPR));
+    let allocation = thaw_core.get_validator_allocation(create_mock_validator_key()).unwrap();
+    assert_eq!(allocation.delegated, U512::from(800u64 * CSPR));
+}
+
+#[test]
+fn test_instant_unstake_pays_immediately() {
+    let (env, mut thaw_core, thcspr_token, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    let rate_before = thaw_core.get_exchange_rate();
+    let balance_before = env.balance_of(&user);
+
+    // Draining half the target → fee halfway between 10 and 300 bps
+    let amount = U512::from(100u64 * CSPR);
+    assert_eq!(thaw_core.get_instant_unstake_fee_bps(amount), 155);
+
+    let outcome = thaw_core.instant_unstake(amount, 155);
+    assert_eq!(outcome.withdrawal_id, None);
+    let payout = outcome.cspr_amount;
+
+    let fee = amount * U512::from(155u64) / U512::from(10000u64);
+    assert_eq!(payout, amount - fee);
+    assert_eq!(env.balance_of(&user), balance_before + payout);
+    assert_eq!(
+        thcspr_token.balance_of(user),
+        U512::from(900u64 * CSPR).as_u128().into()
+    );
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::from(200u64 * CSPR) - payout);
+    assert!(thaw_core.get_user_withdrawals(user).is_empty(), "No withdrawal queued");
+
+    // Fee stays in the pool
+    assert!(thaw_core.get_exchange_rate() > rate_before);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        InstantUnstaked {
+            user,
+            thcspr_burned: amount,
+            cspr_amount: payout,
+            fee,
+            fee_bps: 155,
+        }
+    ));
+}
+
+#[test]
+fn test_instant_unstake_fee_above_limit() {
+    let (env, mut thaw_core, _thcspr_token, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    let result = thaw_core.try_instant_unstake(U512::from(100u64 * CSPR), 100);
+    assert_eq!(result.unwrap_err(), Error::InstantFeeTooHigh.into());
+}
+
+#[test]
+fn test_instant_unstake_falls_back_to_queue() {
+    // More than the buffer holds → regular withdrawal request
+    let (env, mut thaw_core, _thcspr_token, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    let outcome = thaw_core.instant_unstake(U512::from(300u64 * CSPR), 300);
+    assert_eq!(outcome.cspr_amount, U512::zero());
+
+    let withdrawals = thaw_core.get_user_withdrawals(user);
+    assert_eq!(withdrawals.len(), 1);
+    assert_eq!(outcome.withdrawal_id, Some(withdrawals[0].id));
+    assert_eq!(withdrawals[0].cspr_amount, U512::from(300u64 * CSPR));
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::from(200u64 * CSPR));
+}
+
+#[test]
+fn test_instant_unstake_without_buffer() {
+    let (env, mut thaw_core, _thcspr_token, admin, user) = setup();
+
+    env.set_caller(admin);
+    thaw_core.set_liquidity_buffer_config(0, 0, 10, 300);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::zero());
+
+    let outcome = thaw_core.instant_unstake(U512::from(10u64 * CSPR), 300);
+    assert_eq!(outcome.cspr_amount, U512::zero());
+    let withdrawals = thaw_core.get_user_withdrawals(user);
+    assert_eq!(withdrawals.len(), 1);
+    assert_eq!(outcome.withdrawal_id, Some(withdrawals[0].id));
+}
+
+#[test]
+fn test_fee_quote_at_minimum_above_target() {
+    let (env, mut thaw_core, _thcspr_token, admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    // Lower target → buffer sits above it
+    env.set_caller(admin);
+    thaw_core.set_liquidity_buffer_config(1000, 5000, 10, 300);
+    assert_eq!(thaw_core.get_instant_unstake_fee_bps(U512::from(50u64 * CSPR)), 10);
+}
+
+#[test]
+fn test_set_liquidity_buffer_config() {
+    let (env, mut thaw_core, _thcspr_token, admin, _user) = setup();
+
+    env.set_caller(admin);
+    thaw_core.set_liquidity_buffer_config(1500, 2500, 20, 500);
+
+    let config = thaw_core.get_liquidity_buffer_config();
+    assert_eq!(config.target_bps, 1500);
+    assert_eq!(config.stake_share_bps, 2500);
+    assert_eq!(config.min_fee_bps, 20);
+    assert_eq!(config.max_fee_bps, 500);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        LiquidityBufferConfigUpdated {
+            target_bps: 1500,
+            stake_share_bps: 2500,
+            min_fee_bps: 20,
+            max_fee_bps: 500,
+        }
+    ));
+}
+
+#[test]
+fn test_set_liquidity_buffer_config_invalid() {
+    let (env, mut thaw_core, _thcspr_token, admin, user) = setup();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_set_liquidity_buffer_config(10001, 0, 10, 300);
+    assert_eq!(result.unwrap_err(), Error::InvalidBufferConfig.into());
+
+    let result = thaw_core.try_set_liquidity_buffer_config(1000, 1000, 400, 300);
+    assert_eq!(result.unwrap_err(), Error::InvalidBufferConfig.into());
+
+    let result = thaw_core.try_set_liquidity_buffer_config(1000, 1000, 10, 1500);
+    assert_eq!(result.unwrap_err(), Error::FeeTooHigh.into());
+
+    env.set_caller(user);
+    let result = thaw_core.try_set_liquidity_buffer_config(1000, 1000, 10, 300);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
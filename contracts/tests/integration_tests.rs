@@ -24,16 +24,12 @@ fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address, A
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder, will update after token deploy
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -86,7 +82,7 @@ fn test_full_stake_unstake_claim_flow() {
     assert!(!withdrawal.claimed);
 
     // 4. Wait 14h
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // 5. Claims successfully
     let claimed_amount = thaw_core.claim(withdrawal_id);
@@ -131,7 +127,7 @@ fn test_multiple_users_stake_and_unstake() {
     let withdrawal_id2 = thaw_core.unstake(stake2);
 
     // Wait for unbonding
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // Both users claim
     env.set_caller(user1);
@@ -150,7 +146,7 @@ fn test_multiple_users_stake_and_unstake() {
 #[test]
 fn test_first_deposit_exchange_rate() {
     // First deposit always 1:1
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user, _user2) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user, _user2) = setup();
 
     // Initial exchange rate should be 1e18 (1:1)
     let initial_rate = thaw_core.get_exchange_rate();
@@ -171,7 +167,7 @@ fn test_first_deposit_exchange_rate() {
 #[test]
 fn test_empty_pool_after_full_withdraw() {
     // All users withdraw → totals = 0 → next deposit is 1:1
-    let (env, mut thaw_core, thcspr_token, _admin, _treasury, user, _user2) = setup();
+    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user, _user2) = setup();
 
     // User stakes
     env.set_caller(user);
@@ -186,7 +182,7 @@ fn test_empty_pool_after_full_withdraw() {
     assert_eq!(thaw_core.get_total_supply(), U512::zero());
 
     // Wait and claim
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
     thaw_core.claim(withdrawal_id);
 
     // Exchange rate should reset to 1:1 for empty pool
@@ -326,7 +322,7 @@ fn test_admin_pause_during_active_withdrawals() {
     thaw_core.pause();
 
     // Advance time past unbonding
-    env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
 
     // User should still be able to claim
     env.set_caller(user);
@@ -349,7 +345,6 @@ fn test_view_functions() {
     assert_eq!(thaw_core.get_admin(), Some(admin));
     assert_eq!(thaw_core.get_treasury(), Some(treasury));
     assert!(thaw_core.get_validator().is_some());
-    assert!(thaw_core.get_auction().is_some());
 
     // User stakes
     env.set_caller(user);
@@ -402,7 +397,7 @@ fn test_concurrent_operations_same_block() {
 #[test]
 fn test_exchange_rate_precision() {
     // Test that exchange rate maintains precision
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user, _user2) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user, _user2) = setup();
 
     env.set_caller(user);
 
@@ -434,7 +429,7 @@ fn test_full_cycle_multiple_times() {
         let withdrawal_id = thaw_core.unstake(stake_amount);
 
         // Wait and claim
-        env.advance_block_time(UNBONDING_PERIOD_MS + 1);
+        env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
         thaw_core.claim(withdrawal_id);
     }
 
@@ -0,0 +1,197 @@
+//! LendingPool interest accrual tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::Withdrawn;
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+const BASE_RATE_BPS: u64 = 500; // 5% flat borrow APR
+
+/// Helper to setup a pool with 1000 CSPR of liquidity and a collateralised borrower
+///
+/// The rate model is flattened to `BASE_RATE_BPS` and all interest goes to
+/// lenders so it is easy to follow.
+///
+/// Returns (env, lending_pool, lender, borrower).
+fn setup() -> (HostEnv, LendingPoolHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let lender = env.get_account(2);
+    let borrower = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let mut thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+    lending_pool.set_interest_rate_model(BASE_RATE_BPS, 0, 8000, 0);
+    lending_pool.set_reserve_factor(0);
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(1000u64 * CSPR));
+    lending_pool.deposit_collateral(U512::from(500u64 * CSPR));
+
+    (env, lending_pool, lender, borrower)
+}
+
+/// Assert `actual` is within 0.001 CSPR of `expected`
+fn assert_close(actual: U512, expected: U512) {
+    let diff = if actual > expected { actual - expected } else { expected - actual };
+    assert!(diff <= U512::from(CSPR / 1000), "{} != {}", actual, expected);
+}
+
+#[test]
+fn test_no_interest_without_borrows() {
+    let (env, lending_pool, lender, _borrower) = setup();
+
+    env.advance_block_time(YEAR_MS);
+    assert_eq!(lending_pool.get_lender_deposit(lender), U512::from(1000u64 * CSPR));
+    assert_eq!(lending_pool.get_total_deposits(), U512::from(1000u64 * CSPR));
+    assert_eq!(lending_pool.get_borrow_index(), U512::from(EXCHANGE_RATE_PRECISION));
+    assert_eq!(lending_pool.get_supply_apr(), 0);
+}
+
+#[test]
+fn test_debt_accrues_at_borrow_apr() {
+    let (env, mut lending_pool, _lender, borrower) = setup();
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    assert_eq!(lending_pool.get_borrow_apr(), BASE_RATE_BPS);
+
+    env.advance_block_time(YEAR_MS);
+    let (_, debt) = lending_pool.get_position(borrower);
+    assert_eq!(debt, U512::from(105u64 * CSPR));
+    assert_eq!(lending_pool.get_total_borrowed(), U512::from(105u64 * CSPR));
+    assert_eq!(lending_pool.get_total_deposits(), U512::from(1005u64 * CSPR));
+    assert_eq!(
+        lending_pool.get_borrow_index(),
+        U512::from(EXCHANGE_RATE_PRECISION * 105 / 100)
+    );
+
+    // Accrued interest counts towards the borrow limit
+    let max_borrow = lending_pool.get_max_borrow(borrower);
+    let result = lending_pool.try_borrow(max_borrow + 1);
+    assert_eq!(result, Err(LendingError::ExceedsMaxBorrow.into()));
+}
+
+#[test]
+fn test_repay_clears_debt_with_interest() {
+    let (env, mut lending_pool, _lender, borrower) = setup();
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    env.advance_block_time(YEAR_MS / 2);
+
+    // Repaying the principal leaves the interest outstanding
+    lending_pool.with_tokens(U512::from(100u64 * CSPR)).repay();
+    let (_, debt) = lending_pool.get_position(borrower);
+    assert_close(debt, U512::from(5u64 * CSPR / 2));
+
+    lending_pool.with_tokens(U512::from(3u64 * CSPR)).repay();
+    let (_, debt) = lending_pool.get_position(borrower);
+    assert_eq!(debt, U512::zero());
+    assert_eq!(lending_pool.get_total_borrowed(), U512::zero());
+}
+
+#[test]
+fn test_lender_withdraws_interest() {
+    let (env, mut lending_pool, lender, borrower) = setup();
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    // 10% utilization at 5% borrow APR
+    assert_eq!(lending_pool.get_supply_apr(), BASE_RATE_BPS / 10);
+
+    env.advance_block_time(YEAR_MS);
+    lending_pool.with_tokens(U512::from(105u64 * CSPR)).repay();
+
+    let balance = lending_pool.get_lender_deposit(lender);
+    assert_close(balance, U512::from(1005u64 * CSPR));
+
+    env.set_caller(lender);
+    lending_pool.withdraw(balance);
+    assert!(env.emitted_event(
+        &lending_pool,
+        Withdrawn {
+            lender,
+            amount: balance,
+            interest_earned: balance - U512::from(1000u64 * CSPR),
+        }
+    ));
+    assert_eq!(lending_pool.get_lender_deposit(lender), U512::zero());
+}
+
+#[test]
+fn test_interest_shared_pro_rata() {
+    let (env, mut lending_pool, lender, borrower) = setup();
+    let second_lender = env.get_account(4);
+
+    env.set_caller(second_lender);
+    lending_pool.with_tokens(U512::from(500u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(150u64 * CSPR));
+    env.advance_block_time(YEAR_MS);
+
+    // 7.5 CSPR of interest split 2:1
+    assert_close(lending_pool.get_lender_deposit(lender), U512::from(1005u64 * CSPR));
+    assert_close(
+        lending_pool.get_lender_deposit(second_lender),
+        U512::from(5025u64 * CSPR / 10),
+    );
+
+    // A lender joining after the accrual does not share in it
+    let late_lender = env.get_account(5);
+    env.set_caller(late_lender);
+    lending_pool.with_tokens(U512::from(100u64 * CSPR)).deposit();
+    assert_close(lending_pool.get_lender_deposit(late_lender), U512::from(100u64 * CSPR));
+    assert_close(lending_pool.get_lender_deposit(lender), U512::from(1005u64 * CSPR));
+}
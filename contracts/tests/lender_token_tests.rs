@@ -0,0 +1,191 @@
+//! tCSPR lender receipt token tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::Withdrawn;
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::tcspr_token::{TCsprToken, TCsprTokenHostRef, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Helper to setup a pool whose tCSPR token is not linked yet, plus a
+/// borrower holding 1000 thCSPR approved for the pool
+///
+/// Returns (env, lending_pool, lender_token, admin, borrower).
+fn setup_unlinked() -> (HostEnv, LendingPoolHostRef, TCsprTokenHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let borrower = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let mut thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+
+    env.set_caller(borrower);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(1000u64 * CSPR));
+
+    env.set_caller(admin);
+    (env, lending_pool, lender_token, admin, borrower)
+}
+
+/// Helper to setup a linked pool with 1000 CSPR deposited and 100 CSPR borrowed
+///
+/// Returns (env, lending_pool, lender_token, lender, borrower).
+fn setup() -> (HostEnv, LendingPoolHostRef, TCsprTokenHostRef, Address, Address) {
+    let (env, mut lending_pool, lender_token, admin, borrower) = setup_unlinked();
+    let lender = env.get_account(2);
+
+    env.set_caller(admin);
+    lending_pool.set_lender_token(lender_token.address());
+    // Flat 5% APR, all to lenders, keeps the numbers easy to follow
+    lending_pool.set_interest_rate_model(500, 0, 8000, 0);
+    lending_pool.set_reserve_factor(0);
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    lending_pool.deposit_collateral(U512::from(500u64 * CSPR));
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+
+    (env, lending_pool, lender_token, lender, borrower)
+}
+
+#[test]
+fn test_set_lender_token() {
+    let (env, mut lending_pool, lender_token, admin, _borrower) = setup_unlinked();
+    let lender = env.get_account(2);
+
+    env.set_caller(lender);
+    let result = lending_pool.with_tokens(U512::from(100u64 * CSPR)).try_deposit();
+    assert_eq!(result, Err(LendingError::LenderTokenNotSet.into()));
+    let result = lending_pool.try_set_lender_token(lender_token.address());
+    assert_eq!(result, Err(LendingError::NotAdmin.into()));
+
+    env.set_caller(admin);
+    lending_pool.set_lender_token(lender_token.address());
+    assert_eq!(lending_pool.get_lender_token(), Some(lender_token.address()));
+
+    // Cannot be swapped out from under existing holders
+    let result = lending_pool.try_set_lender_token(env.get_account(5));
+    assert_eq!(result, Err(LendingError::LenderTokenAlreadySet.into()));
+}
+
+#[test]
+fn test_deposit_mints_and_withdraw_burns() {
+    let (env, mut lending_pool, lender_token, lender, _borrower) = setup();
+
+    assert_eq!(lender_token.symbol(), "tCSPR");
+    assert_eq!(lender_token.decimals(), 9);
+    assert_eq!(lender_token.balance_of(lender), U256::from(1000u64 * CSPR));
+    assert_eq!(lender_token.total_supply(), U256::from(1000u64 * CSPR));
+    assert_eq!(lending_pool.get_lender_exchange_rate(), U512::from(EXCHANGE_RATE_PRECISION));
+
+    env.set_caller(lender);
+    lending_pool.withdraw(U512::from(400u64 * CSPR));
+    assert_eq!(lender_token.balance_of(lender), U256::from(600u64 * CSPR));
+    assert_eq!(lender_token.total_supply(), U256::from(600u64 * CSPR));
+    assert_eq!(lending_pool.get_lender_deposit(lender), U512::from(600u64 * CSPR));
+
+    let result = lending_pool.try_withdraw(U512::from(601u64 * CSPR));
+    assert_eq!(result, Err(LendingError::InsufficientDeposit.into()));
+}
+
+#[test]
+fn test_only_pool_can_mint() {
+    let (env, _lending_pool, mut lender_token, lender, _borrower) = setup();
+
+    env.set_caller(lender);
+    assert!(lender_token.try_mint(lender, U512::from(CSPR)).is_err());
+    assert!(lender_token.try_burn(lender, U512::from(CSPR)).is_err());
+}
+
+#[test]
+fn test_exchange_rate_grows_with_interest() {
+    let (env, lending_pool, lender_token, lender, _borrower) = setup();
+
+    env.advance_block_time(YEAR_MS);
+    // 5 CSPR of interest on 1000 tCSPR
+    let rate = lending_pool.get_lender_exchange_rate();
+    assert!(rate > U512::from(EXCHANGE_RATE_PRECISION * 1004 / 1000));
+    assert!(rate <= U512::from(EXCHANGE_RATE_PRECISION * 1005 / 1000));
+
+    // A later deposit gets fewer tCSPR for the same CSPR
+    let late_lender = env.get_account(4);
+    env.set_caller(late_lender);
+    lending_pool.with_tokens(U512::from(1005u64 * CSPR)).deposit();
+    let minted = lender_token.balance_of(late_lender);
+    assert!(minted < U256::from(1001u64 * CSPR));
+    assert!(minted >= U256::from(999u64 * CSPR));
+    assert!(lending_pool.get_lender_entry_rate(late_lender) >= rate);
+    assert_eq!(lender_token.balance_of(lender), U256::from(1000u64 * CSPR));
+}
+
+#[test]
+fn test_transferred_tcspr_can_be_withdrawn() {
+    let (env, mut lending_pool, mut lender_token, lender, _borrower) = setup();
+    let recipient = env.get_account(4);
+
+    env.advance_block_time(YEAR_MS);
+    env.set_caller(lender);
+    lender_token.transfer(recipient, U256::from(200u64 * CSPR));
+    assert_eq!(lender_token.balance_of(lender), U256::from(800u64 * CSPR));
+    assert!(lending_pool.get_lender_deposit(recipient) > U512::from(200u64 * CSPR));
+
+    // The recipient never deposited, so its tCSPR are costed at 1:1
+    let value = lending_pool.get_lender_deposit(recipient);
+    env.set_caller(recipient);
+    lending_pool.withdraw(value);
+    assert!(env.emitted_event(
+        &lending_pool,
+        Withdrawn {
+            lender: recipient,
+            amount: value,
+            interest_earned: value - U512::from(200u64 * CSPR),
+        }
+    ));
+    assert_eq!(lender_token.balance_of(recipient), U256::zero());
+}
@@ -0,0 +1,192 @@
+//! LendingPool per-action pause tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::LendingActionPauseUpdated;
+use thaw::lending_pool::{LendingAction, LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::roles::PAUSER_ROLE;
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const ALL_ACTIONS: [LendingAction; 5] = [
+    LendingAction::Deposit,
+    LendingAction::Withdraw,
+    LendingAction::Borrow,
+    LendingAction::Leverage,
+    LendingAction::Liquidate,
+];
+
+/// Helper to setup a pool with a lender and a borrower holding debt
+///
+/// Returns (env, lending_pool, admin, lender, borrower).
+fn setup() -> (HostEnv, LendingPoolHostRef, Address, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let lender = env.get_account(2);
+    let borrower = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let mut thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(1000u64 * CSPR));
+    lending_pool.deposit_collateral(U512::from(500u64 * CSPR));
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+
+    (env, lending_pool, admin, lender, borrower)
+}
+
+#[test]
+fn test_nothing_paused_by_default() {
+    let (_env, lending_pool, _admin, _lender, _borrower) = setup();
+
+    let state = lending_pool.get_pause_state();
+    assert!(!state.deposit && !state.withdraw && !state.borrow);
+    assert!(!state.leverage && !state.liquidate);
+    for action in ALL_ACTIONS {
+        assert!(!lending_pool.is_action_paused(action));
+    }
+}
+
+#[test]
+fn test_pause_single_action() {
+    let (env, mut lending_pool, admin, lender, borrower) = setup();
+
+    env.set_caller(admin);
+    lending_pool.set_action_paused(LendingAction::Borrow, true);
+    assert!(env.emitted_event(
+        &lending_pool,
+        LendingActionPauseUpdated {
+            action: LendingAction::Borrow,
+            paused: true,
+            by: admin,
+        }
+    ));
+    let state = lending_pool.get_pause_state();
+    assert!(state.borrow);
+    assert!(!state.deposit && !state.withdraw && !state.leverage && !state.liquidate);
+
+    env.set_caller(borrower);
+    let result = lending_pool.try_borrow(U512::from(10u64 * CSPR));
+    assert_eq!(result, Err(LendingError::ActionPaused.into()));
+
+    // Other actions are unaffected
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(10u64 * CSPR)).deposit();
+
+    env.set_caller(admin);
+    lending_pool.set_action_paused(LendingAction::Borrow, false);
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(10u64 * CSPR));
+}
+
+#[test]
+fn test_paused_actions_revert() {
+    let (env, mut lending_pool, admin, lender, borrower) = setup();
+
+    env.set_caller(admin);
+    for action in ALL_ACTIONS {
+        lending_pool.set_action_paused(action, true);
+    }
+
+    env.set_caller(lender);
+    let paused = Err(LendingError::ActionPaused.into());
+    assert_eq!(lending_pool.with_tokens(U512::from(CSPR)).try_deposit(), paused);
+    assert_eq!(lending_pool.try_withdraw(U512::from(CSPR)), paused);
+
+    env.set_caller(borrower);
+    assert_eq!(lending_pool.try_withdraw_collateral(U512::from(CSPR)), paused);
+    assert_eq!(lending_pool.try_borrow(U512::from(CSPR)), paused);
+    assert_eq!(
+        lending_pool.with_tokens(U512::from(10u64 * CSPR)).try_leverage_stake(2),
+        Err(LendingError::ActionPaused.into())
+    );
+    assert_eq!(
+        lending_pool.with_tokens(U512::from(CSPR)).try_liquidate(borrower),
+        paused
+    );
+}
+
+#[test]
+fn test_repay_and_top_up_stay_open() {
+    let (env, mut lending_pool, admin, _lender, borrower) = setup();
+
+    env.set_caller(admin);
+    for action in ALL_ACTIONS {
+        lending_pool.set_action_paused(action, true);
+    }
+
+    env.set_caller(borrower);
+    lending_pool.deposit_collateral(U512::from(100u64 * CSPR));
+    lending_pool.with_tokens(U512::from(40u64 * CSPR)).repay();
+
+    let (collateral, borrowed) = lending_pool.get_position(borrower);
+    assert_eq!(collateral, U512::from(600u64 * CSPR));
+    assert_eq!(borrowed, U512::from(60u64 * CSPR));
+}
+
+#[test]
+fn test_set_action_paused_requires_pauser() {
+    let (env, mut lending_pool, admin, _lender, borrower) = setup();
+    let guardian = env.get_account(4);
+
+    env.set_caller(guardian);
+    let result = lending_pool.try_set_action_paused(LendingAction::Deposit, true);
+    assert_eq!(result, Err(LendingError::MissingRole.into()));
+
+    env.set_caller(admin);
+    lending_pool.grant_role(PAUSER_ROLE, guardian);
+
+    env.set_caller(guardian);
+    lending_pool.set_action_paused(LendingAction::Leverage, true);
+    assert!(lending_pool.is_action_paused(LendingAction::Leverage));
+
+    env.set_caller(borrower);
+    let result = lending_pool.with_tokens(U512::from(10u64 * CSPR)).try_leverage_stake(1);
+    assert_eq!(result, Err(LendingError::ActionPaused.into()));
+}
@@ -0,0 +1,359 @@
+//! LendingPool tests for Thaw protocol: lending, borrowing, liquidation and
+//! leveraged staking
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::{Borrowed, Deposited, LeveragedStake, Liquidated, Repaid};
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Helper to setup linked ThawCore, thCSPR, LendingPool and tCSPR
+///
+/// Returns (env, thaw_core, thcspr_token, lending_pool, admin).
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, LendingPoolHostRef, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+
+    (env, thaw_core, thcspr_token, lending_pool, admin)
+}
+
+/// Stake `amount` CSPR for `user` and post all of it as collateral
+fn post_collateral(
+    env: &HostEnv,
+    thaw_core: &ThawCoreHostRef,
+    thcspr_token: &mut ThCsprTokenHostRef,
+    lending_pool: &mut LendingPoolHostRef,
+    user: Address,
+    amount: u64,
+) {
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(amount * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(amount * CSPR));
+    lending_pool.deposit_collateral(U512::from(amount * CSPR));
+}
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, _admin) = setup();
+    let lender = env.get_account(2);
+
+    env.set_caller(lender);
+    let result = lending_pool.with_tokens(U512::zero()).try_deposit();
+    assert_eq!(result, Err(LendingError::AmountMustBePositive.into()));
+
+    lending_pool.with_tokens(U512::from(500u64 * CSPR)).deposit();
+    assert!(env.emitted_event(
+        &lending_pool,
+        Deposited {
+            lender,
+            amount: U512::from(500u64 * CSPR),
+            total_deposits: U512::from(500u64 * CSPR),
+        }
+    ));
+    assert_eq!(lending_pool.get_available_liquidity(), U512::from(500u64 * CSPR));
+
+    lending_pool.withdraw(U512::from(200u64 * CSPR));
+    assert_eq!(lending_pool.get_lender_deposit(lender), U512::from(300u64 * CSPR));
+    assert_eq!(lending_pool.get_total_deposits(), U512::from(300u64 * CSPR));
+}
+
+#[test]
+fn test_borrow_limits() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, _admin) = setup();
+    let borrower = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(100u64 * CSPR)).deposit();
+    post_collateral(&env, &thaw_core, &mut thcspr_token, &mut lending_pool, borrower, 200);
+
+    // 75% collateral factor on 200 CSPR, but only 100 CSPR in the pool
+    assert_eq!(lending_pool.get_max_borrow(borrower), U512::from(150u64 * CSPR));
+    let result = lending_pool.try_borrow(U512::from(120u64 * CSPR));
+    assert_eq!(result, Err(LendingError::InsufficientLiquidity.into()));
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(100u64 * CSPR)).deposit();
+    env.set_caller(borrower);
+    let result = lending_pool.try_borrow(U512::from(151u64 * CSPR));
+    assert_eq!(result, Err(LendingError::ExceedsMaxBorrow.into()));
+
+    lending_pool.borrow(U512::from(150u64 * CSPR));
+    assert!(env.emitted_event(
+        &lending_pool,
+        Borrowed {
+            borrower,
+            amount: U512::from(150u64 * CSPR),
+            total_borrowed: U512::from(150u64 * CSPR),
+            collateral_value: U512::from(200u64 * CSPR),
+        }
+    ));
+    assert_eq!(lending_pool.get_max_borrow(borrower), U512::zero());
+}
+
+#[test]
+fn test_collateral_withdrawal_keeps_position_safe() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, _admin) = setup();
+    let borrower = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(500u64 * CSPR)).deposit();
+    post_collateral(&env, &thaw_core, &mut thcspr_token, &mut lending_pool, borrower, 200);
+    lending_pool.borrow(U512::from(75u64 * CSPR));
+
+    let result = lending_pool.try_withdraw_collateral(U512::from(201u64 * CSPR));
+    assert_eq!(result, Err(LendingError::InsufficientCollateral.into()));
+    let result = lending_pool.try_withdraw_collateral(U512::from(101u64 * CSPR));
+    assert_eq!(result, Err(LendingError::WouldBecomeUndercollateralized.into()));
+
+    lending_pool.withdraw_collateral(U512::from(100u64 * CSPR));
+    assert_eq!(thcspr_token.balance_of(borrower), U256::from(100u64 * CSPR));
+    assert_eq!(
+        lending_pool.get_position(borrower),
+        (U512::from(100u64 * CSPR), U512::from(75u64 * CSPR))
+    );
+}
+
+#[test]
+fn test_repay_refunds_excess() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, _admin) = setup();
+    let borrower = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(500u64 * CSPR)).deposit();
+    post_collateral(&env, &thaw_core, &mut thcspr_token, &mut lending_pool, borrower, 200);
+    lending_pool.borrow(U512::from(50u64 * CSPR));
+
+    let balance_before = env.balance_of(&borrower);
+    lending_pool.with_tokens(U512::from(80u64 * CSPR)).repay();
+    assert_eq!(env.balance_of(&borrower), balance_before - U512::from(50u64 * CSPR));
+    assert!(env.emitted_event(
+        &lending_pool,
+        Repaid {
+            borrower,
+            amount: U512::from(50u64 * CSPR),
+            remaining_debt: U512::zero(),
+        }
+    ));
+    assert_eq!(lending_pool.get_total_borrowed(), U512::zero());
+}
+
+#[test]
+fn test_liquidate_unhealthy_position() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, admin) = setup();
+    let borrower = env.get_account(3);
+    let liquidator = env.get_account(4);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(500u64 * CSPR)).deposit();
+    post_collateral(&env, &thaw_core, &mut thcspr_token, &mut lending_pool, borrower, 200);
+    lending_pool.borrow(U512::from(150u64 * CSPR));
+
+    env.set_caller(liquidator);
+    let result = lending_pool.with_tokens(U512::from(10u64 * CSPR)).try_liquidate(borrower);
+    assert_eq!(result, Err(LendingError::PositionHealthy.into()));
+
+    // 10% APR for a year takes the debt past the 80% liquidation threshold
+    env.set_caller(admin);
+    lending_pool.set_interest_rate_model(1000, 0, 8000, 0);
+    env.advance_block_time(YEAR_MS);
+    assert!(lending_pool.get_health_factor(borrower) < U512::from(EXCHANGE_RATE_PRECISION));
+
+    env.set_caller(liquidator);
+    lending_pool.with_tokens(U512::from(50u64 * CSPR)).liquidate(borrower);
+    // 50 CSPR repaid for 52.5 thCSPR with the 5% bonus
+    assert!(env.emitted_event(
+        &lending_pool,
+        Liquidated {
+            liquidator,
+            borrower,
+            repaid_amount: U512::from(50u64 * CSPR),
+            collateral_seized: U512::from(525u64 * CSPR / 10),
+        }
+    ));
+    assert_eq!(thcspr_token.balance_of(liquidator), U256::from(525u64 * CSPR / 10));
+    // 165 CSPR of debt less 50 repaid, rounded up in the pool's favour
+    let (collateral, debt) = lending_pool.get_position(borrower);
+    assert_eq!(collateral, U512::from(1475u64 * CSPR / 10));
+    assert!(debt >= U512::from(115u64 * CSPR) && debt <= U512::from(115u64 * CSPR) + 1);
+}
+
+#[test]
+fn test_leverage_stake_credits_every_loop() {
+    let (env, _thaw_core, thcspr_token, lending_pool, _admin) = setup();
+    let user = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    // 100 staked, 75 borrowed and staked, 56.25 borrowed and staked
+    env.set_caller(user);
+    let total_thcspr = lending_pool.with_tokens(U512::from(100u64 * CSPR)).leverage_stake(3);
+    let expected_thcspr = U512::from(23125u64 * CSPR / 100);
+    let expected_debt = U512::from(13125u64 * CSPR / 100);
+    assert_eq!(total_thcspr, expected_thcspr);
+    assert_eq!(lending_pool.get_position(user), (expected_thcspr, expected_debt));
+    assert!(env.emitted_event(
+        &lending_pool,
+        LeveragedStake {
+            user,
+            initial_amount: U512::from(100u64 * CSPR),
+            total_staked: expected_thcspr,
+            total_thcspr: expected_thcspr,
+            total_borrowed: expected_debt,
+            leverage_loops: 3,
+        }
+    ));
+
+    // The pool holds exactly the thCSPR it owes as collateral
+    assert_eq!(
+        thcspr_token.balance_of(lending_pool.address()),
+        U256::from(expected_thcspr.as_u128())
+    );
+    assert!(lending_pool.get_health_factor(user) >= U512::from(EXCHANGE_RATE_PRECISION));
+}
+
+#[test]
+fn test_leveraged_thcspr_returns_to_user() {
+    let (env, _thaw_core, thcspr_token, mut lending_pool, _admin) = setup();
+    let user = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    let total_thcspr = lending_pool.with_tokens(U512::from(100u64 * CSPR)).leverage_stake(4);
+    let (_, debt) = lending_pool.get_position(user);
+    lending_pool.with_tokens(debt).repay();
+    lending_pool.withdraw_collateral(total_thcspr);
+
+    assert_eq!(thcspr_token.balance_of(user), U256::from(total_thcspr.as_u128()));
+    assert_eq!(thcspr_token.balance_of(lending_pool.address()), U256::zero());
+    assert_eq!(lending_pool.get_position(user), (U512::zero(), U512::zero()));
+}
+
+#[test]
+fn test_leverage_stake_with_limited_liquidity() {
+    let (env, _thaw_core, _thcspr_token, lending_pool, _admin) = setup();
+    let user = env.get_account(3);
+
+    // Only 100 CSPR to lend: 75 on the first loop, the last 25 on the second
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(100u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    let total_thcspr = lending_pool.with_tokens(U512::from(100u64 * CSPR)).leverage_stake(4);
+    assert_eq!(total_thcspr, U512::from(200u64 * CSPR));
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::from(200u64 * CSPR), U512::from(100u64 * CSPR))
+    );
+    assert_eq!(lending_pool.get_available_liquidity(), U512::zero());
+    assert!(env.emitted_event(
+        &lending_pool,
+        LeveragedStake {
+            user,
+            initial_amount: U512::from(100u64 * CSPR),
+            total_staked: U512::from(200u64 * CSPR),
+            total_thcspr: U512::from(200u64 * CSPR),
+            total_borrowed: U512::from(100u64 * CSPR),
+            leverage_loops: 3,
+        }
+    ));
+}
+
+#[test]
+fn test_leverage_stops_below_min_stake() {
+    let (env, _thaw_core, _thcspr_token, lending_pool, _admin) = setup();
+    let user = env.get_account(3);
+
+    // After the first 75 CSPR borrow only 5 CSPR is left, below the minimum stake
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(80u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    let total_thcspr = lending_pool.with_tokens(U512::from(100u64 * CSPR)).leverage_stake(3);
+    assert_eq!(total_thcspr, U512::from(175u64 * CSPR));
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::from(175u64 * CSPR), U512::from(75u64 * CSPR))
+    );
+    assert_eq!(lending_pool.get_available_liquidity(), U512::from(5u64 * CSPR));
+}
+
+#[test]
+fn test_leverage_stake_validation() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, admin) = setup();
+    let user = env.get_account(3);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    let invalid_loops = Err(LendingError::InvalidLoopCount.into());
+    let result = lending_pool.with_tokens(U512::from(100u64 * CSPR)).try_leverage_stake(0);
+    assert_eq!(result, invalid_loops);
+    let result = lending_pool.with_tokens(U512::from(100u64 * CSPR)).try_leverage_stake(5);
+    assert_eq!(result, invalid_loops);
+    let result = lending_pool.with_tokens(U512::zero()).try_leverage_stake(2);
+    assert_eq!(result, Err(LendingError::AmountMustBePositive.into()));
+
+    // An existing position that is already underwater cannot lever up
+    post_collateral(&env, &thaw_core, &mut thcspr_token, &mut lending_pool, user, 100);
+    lending_pool.borrow(U512::from(75u64 * CSPR));
+    env.set_caller(admin);
+    lending_pool.set_interest_rate_model(10_000, 0, 8000, 0);
+    env.advance_block_time(YEAR_MS);
+
+    env.set_caller(user);
+    let result = lending_pool.with_tokens(U512::from(10u64 * CSPR)).try_leverage_stake(1);
+    assert_eq!(result, Err(LendingError::WouldBecomeUndercollateralized.into()));
+}
@@ -0,0 +1,239 @@
+//! LendingPool target-leverage tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::{Error, LendingError};
+use thaw::events::{CapExemptionUpdated, LeveragedStake, StakingAprUpdated};
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::roles::RISK_MANAGER_ROLE;
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup a linked pool with 1000 CSPR of liquidity
+///
+/// Returns (env, lending_pool, thaw_core, admin, user).
+fn setup() -> (HostEnv, LendingPoolHostRef, ThawCoreHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let lender = env.get_account(2);
+    let user = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(user);
+    (env, lending_pool, thaw_core, admin, user)
+}
+
+#[test]
+fn test_leverage_stake_to_reaches_target() {
+    let (env, lending_pool, _thaw_core, _admin, user) = setup();
+
+    // 2.5x on 100 CSPR: 150 borrowed, 250 staked in one go
+    let min_out = U512::from(250u64 * CSPR);
+    let thcspr = lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(25_000, min_out);
+    assert_eq!(thcspr, U512::from(250u64 * CSPR));
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::from(250u64 * CSPR), U512::from(150u64 * CSPR))
+    );
+    assert!(env.emitted_event(
+        &lending_pool,
+        LeveragedStake {
+            user,
+            initial_amount: U512::from(100u64 * CSPR),
+            total_staked: U512::from(250u64 * CSPR),
+            total_thcspr: U512::from(250u64 * CSPR),
+            total_borrowed: U512::from(150u64 * CSPR),
+            leverage_loops: 1,
+        }
+    ));
+}
+
+#[test]
+fn test_pool_exempt_from_per_address_cap() {
+    // Every leveraged position is staked by the pool itself
+    let (env, lending_pool, mut thaw_core, admin, user) = setup();
+    let user2 = env.get_account(4);
+
+    env.set_caller(admin);
+    thaw_core.set_stake_caps(U512::zero(), U512::from(300u64 * CSPR));
+
+    env.set_caller(user);
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(25_000, U512::zero());
+    env.set_caller(user2);
+    let result = lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .try_leverage_stake_to(25_000, U512::zero());
+    assert_eq!(result.unwrap_err(), Error::AddressCapExceeded.into());
+
+    env.set_caller(admin);
+    thaw_core.set_cap_exempt(lending_pool.address(), true);
+    assert!(thaw_core.is_cap_exempt(lending_pool.address()));
+    assert!(env.emitted_event(
+        &thaw_core,
+        CapExemptionUpdated {
+            account: lending_pool.address(),
+            exempt: true,
+        }
+    ));
+
+    env.set_caller(user2);
+    lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .leverage_stake_to(25_000, U512::zero());
+    assert_eq!(
+        lending_pool.get_position(user2),
+        (U512::from(250u64 * CSPR), U512::from(150u64 * CSPR))
+    );
+
+    // Direct stakers are still capped
+    env.set_caller(user2);
+    let result = thaw_core.with_tokens(U512::from(301u64 * CSPR)).try_stake();
+    assert_eq!(result.unwrap_err(), Error::AddressCapExceeded.into());
+}
+
+#[test]
+fn test_leverage_bounds() {
+    let (_env, lending_pool, _thaw_core, _admin, user) = setup();
+    let amount = U512::from(100u64 * CSPR);
+
+    // 75% collateral factor allows up to 4x
+    assert_eq!(lending_pool.get_max_leverage(), 40_000);
+    let invalid = Err(LendingError::InvalidLeverage.into());
+    let result = lending_pool.with_tokens(amount).try_leverage_stake_to(9_999, U512::zero());
+    assert_eq!(result, invalid);
+    let result = lending_pool.with_tokens(amount).try_leverage_stake_to(40_001, U512::zero());
+    assert_eq!(result, invalid);
+
+    // 1x is a plain stake posted as collateral, 4x is the limit
+    lending_pool.with_tokens(amount).leverage_stake_to(10_000, U512::zero());
+    assert_eq!(lending_pool.get_position(user), (amount, U512::zero()));
+    lending_pool.with_tokens(amount).leverage_stake_to(40_000, U512::zero());
+    assert_eq!(
+        lending_pool.get_position(user),
+        (U512::from(500u64 * CSPR), U512::from(300u64 * CSPR))
+    );
+    assert!(lending_pool.get_health_factor(user) > U512::from(EXCHANGE_RATE_PRECISION));
+}
+
+#[test]
+fn test_leverage_stake_to_checks() {
+    let (_env, lending_pool, _thaw_core, _admin, _user) = setup();
+
+    let result = lending_pool
+        .with_tokens(U512::from(100u64 * CSPR))
+        .try_leverage_stake_to(25_000, U512::from(251u64 * CSPR));
+    assert_eq!(result, Err(LendingError::SlippageExceeded.into()));
+
+    // 3x on 600 CSPR needs 1200 CSPR from a 1000 CSPR pool
+    let result = lending_pool
+        .with_tokens(U512::from(600u64 * CSPR))
+        .try_leverage_stake_to(30_000, U512::zero());
+    assert_eq!(result, Err(LendingError::InsufficientLiquidity.into()));
+
+    let result = lending_pool.with_tokens(U512::zero()).try_leverage_stake_to(20_000, U512::zero());
+    assert_eq!(result, Err(LendingError::AmountMustBePositive.into()));
+}
+
+#[test]
+fn test_preview_matches_position() {
+    let (_env, lending_pool, _thaw_core, _admin, user) = setup();
+    let amount = U512::from(100u64 * CSPR);
+
+    let preview = lending_pool.preview_leverage(amount, 25_000);
+    assert_eq!(preview.collateral, U512::from(250u64 * CSPR));
+    assert_eq!(preview.debt, U512::from(150u64 * CSPR));
+    // 250 * 80% / 150
+    assert_eq!(preview.health_factor, U512::from(EXCHANGE_RATE_PRECISION * 4 / 3));
+    // 15% utilization on the default curve: 2% + 4% * 15 / 80
+    assert_eq!(preview.borrow_apr_bps, 275);
+    // 8.5% on 2.5x less 2.75% on 1.5x
+    assert_eq!(preview.net_apr_bps, 1712);
+
+    lending_pool.with_tokens(amount).leverage_stake_to(25_000, preview.collateral);
+    assert_eq!(lending_pool.get_position(user), (preview.collateral, preview.debt));
+    assert_eq!(lending_pool.get_health_factor(user), preview.health_factor);
+    assert_eq!(lending_pool.get_borrow_apr(), preview.borrow_apr_bps);
+}
+
+#[test]
+fn test_staking_apr_drives_net_apr() {
+    let (env, mut lending_pool, _thaw_core, admin, user) = setup();
+
+    assert_eq!(lending_pool.get_staking_apr(), 850);
+    let result = lending_pool.try_set_staking_apr(100);
+    assert_eq!(result, Err(LendingError::MissingRole.into()));
+
+    env.set_caller(admin);
+    lending_pool.grant_role(RISK_MANAGER_ROLE, user);
+    env.set_caller(user);
+    let result = lending_pool.try_set_staking_apr(10_001);
+    assert_eq!(result, Err(LendingError::InvalidParameter.into()));
+
+    lending_pool.set_staking_apr(100);
+    assert!(env.emitted_event(
+        &lending_pool,
+        StakingAprUpdated {
+            old_apr_bps: 850,
+            new_apr_bps: 100,
+        }
+    ));
+
+    // 1% staking on 4x cannot pay 3.5% on 3x of borrowing
+    let preview = lending_pool.preview_leverage(U512::from(100u64 * CSPR), 40_000);
+    assert_eq!(preview.borrow_apr_bps, 350);
+    assert_eq!(preview.net_apr_bps, -650);
+
+    let result = lending_pool.try_preview_leverage(U512::from(100u64 * CSPR), 50_000);
+    assert_eq!(result, Err(LendingError::InvalidLeverage.into()));
+}
@@ -0,0 +1,234 @@
+//! LendingPool interest rate model tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+use proptest::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::{InterestRateModelUpdated, OperationQueued};
+use thaw::interest_rate::{InterestRateModel, MAX_BORROW_RATE_BPS};
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::roles::RISK_MANAGER_ROLE;
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+use thaw::timelock::TimelockedCall;
+
+use test_utils::*;
+
+/// Helper to setup linked contracts
+///
+/// Returns (env, thaw_core, thcspr_token, lending_pool, admin).
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, LendingPoolHostRef, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+
+    (env, thaw_core, thcspr_token, lending_pool, admin)
+}
+
+#[test]
+fn test_default_model_curve() {
+    let (_env, _thaw_core, _thcspr_token, lending_pool, _admin) = setup();
+
+    assert_eq!(lending_pool.get_interest_rate_model(), InterestRateModel::DEFAULT);
+    // 2% base, +4% up to the 80% kink, +75% above it
+    assert_eq!(lending_pool.get_borrow_rate_at(0), 200);
+    assert_eq!(lending_pool.get_borrow_rate_at(4000), 400);
+    assert_eq!(lending_pool.get_borrow_rate_at(8000), 600);
+    assert_eq!(lending_pool.get_borrow_rate_at(9000), 4350);
+    assert_eq!(lending_pool.get_borrow_rate_at(10_000), 8100);
+    assert_eq!(lending_pool.get_borrow_rate_at(20_000), 8100);
+}
+
+#[test]
+fn test_rates_follow_utilization() {
+    let (env, thaw_core, mut thcspr_token, mut lending_pool, _admin) = setup();
+    let borrower = env.get_account(3);
+
+    // An empty pool charges the base rate and pays nothing
+    assert_eq!(lending_pool.get_utilization(), 0);
+    assert_eq!(lending_pool.get_borrow_apr(), 200);
+    assert_eq!(lending_pool.get_supply_apr(), 0);
+
+    env.set_caller(env.get_account(2));
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(1000u64 * CSPR));
+    lending_pool.deposit_collateral(U512::from(1000u64 * CSPR));
+
+    lending_pool.borrow(U512::from(400u64 * CSPR));
+    assert_eq!(lending_pool.get_utilization(), 4000);
+    assert_eq!(lending_pool.get_borrow_apr(), 400);
+    // Lenders get 90% of it after the default reserve factor
+    assert_eq!(lending_pool.get_supply_apr(), 144);
+
+    lending_pool.borrow(U512::from(300u64 * CSPR));
+    assert_eq!(lending_pool.get_utilization(), 7000);
+    assert_eq!(lending_pool.get_borrow_apr(), 550);
+    assert_eq!(lending_pool.get_supply_apr(), 346);
+
+    // Interest accrues at the utilization-based rate
+    env.advance_block_time(365 * 24 * 60 * 60 * 1000);
+    let (_, debt) = lending_pool.get_position(borrower);
+    assert_eq!(debt, U512::from(7385u64 * CSPR / 10));
+}
+
+#[test]
+fn test_set_interest_rate_model() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, admin) = setup();
+    let risk_manager = env.get_account(3);
+
+    env.set_caller(risk_manager);
+    let result = lending_pool.try_set_interest_rate_model(100, 500, 9000, 5000);
+    assert_eq!(result, Err(LendingError::MissingRole.into()));
+
+    env.set_caller(admin);
+    lending_pool.grant_role(RISK_MANAGER_ROLE, risk_manager);
+
+    env.set_caller(risk_manager);
+    lending_pool.set_interest_rate_model(100, 500, 9000, 5000);
+    let model = InterestRateModel {
+        base_rate_bps: 100,
+        slope1_bps: 500,
+        optimal_utilization_bps: 9000,
+        slope2_bps: 5000,
+    };
+    assert_eq!(lending_pool.get_interest_rate_model(), model);
+    assert_eq!(lending_pool.get_borrow_rate_at(9000), 600);
+    assert!(env.emitted_event(
+        &lending_pool,
+        InterestRateModelUpdated {
+            base_rate_bps: 100,
+            slope1_bps: 500,
+            optimal_utilization_bps: 9000,
+            slope2_bps: 5000,
+        }
+    ));
+}
+
+#[test]
+fn test_invalid_models_rejected() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, admin) = setup();
+    let invalid = Err(LendingError::InvalidRateModel.into());
+
+    env.set_caller(admin);
+    // Kink at 0% or 100%
+    assert_eq!(lending_pool.try_set_interest_rate_model(200, 400, 0, 7500), invalid);
+    assert_eq!(lending_pool.try_set_interest_rate_model(200, 400, 10_000, 7500), invalid);
+    // Rate at full utilization above the cap
+    assert_eq!(
+        lending_pool.try_set_interest_rate_model(200, 400, 8000, MAX_BORROW_RATE_BPS),
+        invalid
+    );
+    assert_eq!(
+        lending_pool.try_set_interest_rate_model(u64::MAX, 1, 8000, 0),
+        invalid
+    );
+    assert_eq!(lending_pool.get_interest_rate_model(), InterestRateModel::DEFAULT);
+}
+
+#[test]
+fn test_model_change_is_timelocked() {
+    let (env, _thaw_core, _thcspr_token, mut lending_pool, admin) = setup();
+    let delay_ms = 24 * 60 * 60 * 1000;
+
+    env.set_caller(admin);
+    lending_pool.set_timelock_delay(delay_ms);
+    lending_pool.set_interest_rate_model(0, 1000, 5000, 20_000);
+    assert_eq!(lending_pool.get_interest_rate_model(), InterestRateModel::DEFAULT);
+
+    let queued = lending_pool.get_queued_operations();
+    assert_eq!(
+        queued[0].call,
+        TimelockedCall::SetInterestRateModel {
+            base_rate_bps: 0,
+            slope1_bps: 1000,
+            optimal_utilization_bps: 5000,
+            slope2_bps: 20_000,
+        }
+    );
+    assert!(env.emitted_event(
+        &lending_pool,
+        OperationQueued {
+            id: queued[0].id,
+            eta: queued[0].eta,
+        }
+    ));
+
+    env.advance_block_time(delay_ms);
+    lending_pool.execute_operation(queued[0].id);
+    assert_eq!(lending_pool.get_borrow_rate_at(5000), 1000);
+    assert_eq!(lending_pool.get_borrow_rate_at(10_000), 21_000);
+}
+
+fn valid_model() -> impl Strategy<Value = InterestRateModel> {
+    (0u64..10_000, 0u64..10_000, 1u64..10_000, 0u64..80_000).prop_map(
+        |(base_rate_bps, slope1_bps, optimal_utilization_bps, slope2_bps)| InterestRateModel {
+            base_rate_bps,
+            slope1_bps,
+            optimal_utilization_bps,
+            slope2_bps,
+        },
+    )
+}
+
+proptest! {
+    /// The borrow rate never falls as utilization rises and stays within its bounds
+    #[test]
+    fn prop_rate_is_monotonic(
+        model in valid_model(),
+        low in 0u64..=10_000,
+        high in 0u64..=10_000,
+    ) {
+        prop_assume!(model.is_valid());
+        let (low, high) = (low.min(high), low.max(high));
+        let max_rate = model.base_rate_bps + model.slope1_bps + model.slope2_bps;
+        prop_assert!(model.borrow_rate(low) <= model.borrow_rate(high));
+        prop_assert!(model.borrow_rate(low) >= model.base_rate_bps);
+        prop_assert!(model.borrow_rate(high) <= max_rate);
+        prop_assert_eq!(model.borrow_rate(10_000), max_rate);
+    }
+}
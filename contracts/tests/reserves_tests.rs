@@ -0,0 +1,265 @@
+//! LendingPool reserve factor and protocol reserve tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::LendingError;
+use thaw::events::{BadDebtCovered, ReserveFactorUpdated, ReservesWithdrawn};
+use thaw::fee_splitter::FeeRecipient;
+use thaw::lending_pool::{
+    LendingPool, LendingPoolHostRef, LendingPoolInitArgs, MAX_RESERVE_FACTOR_BPS,
+};
+use thaw::roles::FEE_MANAGER_ROLE;
+use thaw::tcspr_token::{TCsprToken, TCsprTokenInitArgs};
+use thaw::thaw_core::{ThawCore, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Helper to setup a pool with 1000 CSPR of liquidity and a borrower with
+/// 500 thCSPR of collateral, at a flat 5% APR and the default reserve factor
+///
+/// Returns (env, lending_pool, admin, lender, borrower).
+fn setup() -> (HostEnv, LendingPoolHostRef, Address, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let lender = env.get_account(2);
+    let borrower = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+    let mut thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+    let lender_token = TCsprToken::deploy(
+        &env,
+        TCsprTokenInitArgs {
+            minter: lending_pool.address(),
+        },
+    );
+    lending_pool.set_lender_token(lender_token.address());
+    lending_pool.set_interest_rate_model(500, 0, 8000, 0);
+
+    env.set_caller(lender);
+    lending_pool.with_tokens(U512::from(1000u64 * CSPR)).deposit();
+
+    env.set_caller(borrower);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thcspr_token.approve(lending_pool.address(), U256::from(1000u64 * CSPR));
+    lending_pool.deposit_collateral(U512::from(500u64 * CSPR));
+
+    (env, lending_pool, admin, lender, borrower)
+}
+
+#[test]
+fn test_reserves_take_their_share_of_interest() {
+    let (env, mut lending_pool, _admin, lender, borrower) = setup();
+
+    assert_eq!(lending_pool.get_reserve_factor(), 1000);
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    // 5% borrow APR at 10% utilization, 90% of it to lenders
+    assert_eq!(lending_pool.get_supply_apr(), 45);
+
+    env.advance_block_time(YEAR_MS);
+    assert_eq!(lending_pool.get_reserves(), U512::from(CSPR / 2));
+    assert_eq!(lending_pool.get_total_deposits(), U512::from(10045u64 * CSPR / 10));
+    assert_eq!(lending_pool.get_total_borrowed(), U512::from(105u64 * CSPR));
+
+    // Accrual moves the pending share into stored reserves
+    lending_pool.with_tokens(U512::from(105u64 * CSPR)).repay();
+    assert_eq!(lending_pool.get_reserves(), U512::from(CSPR / 2));
+    assert!(lending_pool.get_lender_deposit(lender) < U512::from(10045u64 * CSPR / 10));
+    assert!(lending_pool.get_lender_deposit(lender) > U512::from(1004u64 * CSPR));
+}
+
+#[test]
+fn test_withdraw_reserves() {
+    let (env, mut lending_pool, admin, _lender, borrower) = setup();
+    let treasury = env.get_account(1);
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    env.advance_block_time(YEAR_MS);
+    lending_pool.with_tokens(U512::from(105u64 * CSPR)).repay();
+
+    let result = lending_pool.try_withdraw_reserves(treasury, U512::from(CSPR / 2));
+    assert_eq!(result, Err(LendingError::NotAdmin.into()));
+
+    env.set_caller(admin);
+    let result = lending_pool.try_withdraw_reserves(treasury, U512::zero());
+    assert_eq!(result, Err(LendingError::AmountMustBePositive.into()));
+    let result = lending_pool.try_withdraw_reserves(treasury, U512::from(CSPR));
+    assert_eq!(result, Err(LendingError::InsufficientReserves.into()));
+
+    // No fee recipients needed to withdraw to an address
+    let balance_before = env.balance_of(&treasury);
+    lending_pool.withdraw_reserves(treasury, U512::from(CSPR / 4));
+    assert_eq!(env.balance_of(&treasury), balance_before + U512::from(CSPR / 4));
+    assert_eq!(lending_pool.get_reserves(), U512::from(CSPR / 4));
+    assert_eq!(lending_pool.get_fee_accrued(treasury), U512::from(CSPR / 4));
+    assert!(env.emitted_event(
+        &lending_pool,
+        ReservesWithdrawn {
+            to: treasury,
+            amount: U512::from(CSPR / 4),
+            remaining_reserves: U512::from(CSPR / 4),
+        }
+    ));
+}
+
+#[test]
+fn test_distribute_reserves_between_recipients() {
+    let (env, mut lending_pool, admin, _lender, borrower) = setup();
+    let treasury = env.get_account(1);
+    let insurance = env.get_account(5);
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    env.advance_block_time(YEAR_MS);
+    lending_pool.with_tokens(U512::from(105u64 * CSPR)).repay();
+
+    env.set_caller(admin);
+    let result = lending_pool.try_distribute_reserves(U512::from(CSPR / 2));
+    assert_eq!(result, Err(LendingError::FeeRecipientsNotSet.into()));
+
+    lending_pool.set_fee_recipients(vec![
+        FeeRecipient {
+            account: treasury,
+            share_bps: 6000,
+        },
+        FeeRecipient {
+            account: insurance,
+            share_bps: 4000,
+        },
+    ]);
+
+    let treasury_before = env.balance_of(&treasury);
+    let insurance_before = env.balance_of(&insurance);
+    lending_pool.distribute_reserves(U512::from(CSPR / 2));
+
+    assert_eq!(env.balance_of(&treasury), treasury_before + U512::from(3 * CSPR / 10));
+    assert_eq!(env.balance_of(&insurance), insurance_before + U512::from(2 * CSPR / 10));
+    assert_eq!(lending_pool.get_reserves(), U512::zero());
+    assert_eq!(lending_pool.get_fee_accrued(insurance), U512::from(2 * CSPR / 10));
+    assert!(env.emitted_event(
+        &lending_pool,
+        ReservesWithdrawn {
+            to: insurance,
+            amount: U512::from(2 * CSPR / 10),
+            remaining_reserves: U512::zero(),
+        }
+    ));
+}
+
+#[test]
+fn test_set_reserve_factor() {
+    let (env, mut lending_pool, admin, _lender, borrower) = setup();
+    let fee_manager = env.get_account(4);
+
+    env.set_caller(fee_manager);
+    let result = lending_pool.try_set_reserve_factor(2000);
+    assert_eq!(result, Err(LendingError::MissingRole.into()));
+
+    env.set_caller(admin);
+    lending_pool.grant_role(FEE_MANAGER_ROLE, fee_manager);
+
+    env.set_caller(fee_manager);
+    let result = lending_pool.try_set_reserve_factor(MAX_RESERVE_FACTOR_BPS + 1);
+    assert_eq!(result, Err(LendingError::InvalidReserveFactor.into()));
+
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(100u64 * CSPR));
+    env.advance_block_time(YEAR_MS);
+
+    // Interest so far is split at the old factor
+    env.set_caller(fee_manager);
+    lending_pool.set_reserve_factor(MAX_RESERVE_FACTOR_BPS);
+    assert_eq!(lending_pool.get_reserve_factor(), MAX_RESERVE_FACTOR_BPS);
+    assert!(env.emitted_event(
+        &lending_pool,
+        ReserveFactorUpdated {
+            old_reserve_factor_bps: 1000,
+            new_reserve_factor_bps: MAX_RESERVE_FACTOR_BPS,
+        }
+    ));
+    assert_eq!(lending_pool.get_reserves(), U512::from(CSPR / 2));
+
+    // 5.25 CSPR of interest on 105 CSPR of debt, half to reserves
+    env.advance_block_time(YEAR_MS);
+    assert_eq!(lending_pool.get_reserves(), U512::from(CSPR / 2 + 2625 * CSPR / 1000));
+}
+
+#[test]
+fn test_bad_debt_covered_by_reserves_first() {
+    let (env, mut lending_pool, admin, lender, borrower) = setup();
+    let liquidator = env.get_account(4);
+
+    // 100% APR so the debt outgrows the collateral
+    env.set_caller(admin);
+    lending_pool.set_interest_rate_model(10_000, 0, 8000, 0);
+    env.set_caller(borrower);
+    lending_pool.borrow(U512::from(375u64 * CSPR));
+
+    // Two years: 750 CSPR of interest, 75 CSPR of it to reserves
+    env.advance_block_time(2 * YEAR_MS);
+    assert_eq!(lending_pool.get_reserves(), U512::from(75u64 * CSPR));
+    let deposits_before = lending_pool.get_total_deposits();
+    assert_eq!(deposits_before, U512::from(1675u64 * CSPR));
+
+    // Repaying half the debt seizes all the collateral and leaves 562.5 CSPR
+    env.set_caller(liquidator);
+    lending_pool.with_tokens(U512::from(5625u64 * CSPR / 10)).liquidate(borrower);
+
+    let bad_debt = U512::from(5625u64 * CSPR / 10);
+    let lender_loss = bad_debt - U512::from(75u64 * CSPR);
+    assert!(env.emitted_event(
+        &lending_pool,
+        BadDebtCovered {
+            borrower,
+            bad_debt,
+            covered_by_reserves: U512::from(75u64 * CSPR),
+            lender_loss,
+        }
+    ));
+    assert_eq!(lending_pool.get_position(borrower), (U512::zero(), U512::zero()));
+    assert_eq!(lending_pool.get_reserves(), U512::zero());
+    assert_eq!(lending_pool.get_total_borrowed(), U512::zero());
+    assert_eq!(lending_pool.get_total_deposits(), deposits_before - lender_loss);
+
+    // The whole pool is now idle CSPR the lender can take out
+    let balance = lending_pool.get_lender_deposit(lender);
+    assert_eq!(lending_pool.get_available_liquidity(), deposits_before - lender_loss);
+    env.set_caller(lender);
+    lending_pool.withdraw(balance);
+}
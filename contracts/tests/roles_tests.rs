@@ -0,0 +1,213 @@
+//! Role-based access control tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv};
+use odra::prelude::*;
+use odra_modules::access::events::{RoleGranted, RoleRevoked};
+
+use thaw::errors::{Error, LendingError};
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::roles::{
+    FEE_MANAGER_ROLE, KEEPER_ROLE, PAUSER_ROLE, RISK_MANAGER_ROLE, VALIDATOR_MANAGER_ROLE,
+};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup linked ThawCore and LendingPool
+fn setup() -> (HostEnv, ThawCoreHostRef, LendingPoolHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let operator = env.get_account(3);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    let mut lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+    disable_lending_timelock(&env, &mut lending_pool);
+
+    (env, thaw_core, lending_pool, admin, operator)
+}
+
+#[test]
+fn test_pauser_can_only_pause() {
+    let (env, mut thaw_core, _lending_pool, admin, operator) = setup();
+
+    env.set_caller(admin);
+    thaw_core.grant_role(PAUSER_ROLE, operator);
+    assert!(thaw_core.has_role(PAUSER_ROLE, operator));
+    assert!(env.emitted_event(
+        &thaw_core,
+        RoleGranted {
+            role: PAUSER_ROLE,
+            address: operator,
+            sender: admin,
+        }
+    ));
+
+    env.set_caller(operator);
+    thaw_core.pause();
+    assert!(thaw_core.is_paused());
+    thaw_core.unpause();
+    assert!(!thaw_core.is_paused());
+
+    // Pause rights do not include fee or validator control
+    assert_eq!(thaw_core.try_set_protocol_fee(500), Err(Error::MissingRole.into()));
+    assert_eq!(
+        thaw_core.try_add_validator(other_validators(&env, 1)[0].clone(), 5000),
+        Err(Error::MissingRole.into())
+    );
+}
+
+#[test]
+fn test_each_manager_role_gates_its_functions() {
+    let (env, mut thaw_core, _lending_pool, admin, operator) = setup();
+    let new_validator = other_validators(&env, 1)[0].clone();
+
+    env.set_caller(admin);
+    thaw_core.grant_role(FEE_MANAGER_ROLE, operator);
+    env.set_caller(operator);
+    thaw_core.set_protocol_fee(500);
+    assert_eq!(thaw_core.get_protocol_fee_bps(), 500);
+    assert_eq!(thaw_core.try_set_min_stake(U512::from(CSPR)), Err(Error::MissingRole.into()));
+
+    env.set_caller(admin);
+    thaw_core.grant_role(RISK_MANAGER_ROLE, operator);
+    env.set_caller(operator);
+    thaw_core.set_min_stake(U512::from(CSPR));
+    assert_eq!(thaw_core.get_min_stake(), U512::from(CSPR));
+    assert_eq!(
+        thaw_core.try_add_validator(new_validator.clone(), 5000),
+        Err(Error::MissingRole.into())
+    );
+
+    env.set_caller(admin);
+    thaw_core.grant_role(VALIDATOR_MANAGER_ROLE, operator);
+    env.set_caller(operator);
+    thaw_core.add_validator(new_validator, 5000);
+    assert_eq!(thaw_core.get_validators().len(), 2);
+
+    // Admin-only operations stay with the admin
+    assert_eq!(thaw_core.try_propose_admin(operator), Err(Error::NotAdmin.into()));
+    assert_eq!(
+        thaw_core.try_grant_role(PAUSER_ROLE, operator),
+        Err(Error::NotAdmin.into())
+    );
+}
+
+#[test]
+fn test_revoke_and_renounce_role() {
+    let (env, mut thaw_core, _lending_pool, admin, operator) = setup();
+
+    env.set_caller(admin);
+    thaw_core.grant_role(PAUSER_ROLE, operator);
+    thaw_core.revoke_role(PAUSER_ROLE, operator);
+    assert!(!thaw_core.has_role(PAUSER_ROLE, operator));
+    assert!(env.emitted_event(
+        &thaw_core,
+        RoleRevoked {
+            role: PAUSER_ROLE,
+            address: operator,
+            sender: admin,
+        }
+    ));
+
+    env.set_caller(operator);
+    assert_eq!(thaw_core.try_pause(), Err(Error::MissingRole.into()));
+
+    env.set_caller(admin);
+    thaw_core.grant_role(PAUSER_ROLE, operator);
+    env.set_caller(operator);
+    thaw_core.renounce_role(PAUSER_ROLE);
+    assert!(!thaw_core.has_role(PAUSER_ROLE, operator));
+    assert_eq!(thaw_core.try_pause(), Err(Error::MissingRole.into()));
+}
+
+#[test]
+fn test_grant_unknown_role_fails() {
+    let (env, mut thaw_core, mut lending_pool, admin, operator) = setup();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_grant_role([7u8; 32], operator);
+    assert_eq!(result, Err(Error::InvalidRole.into()));
+    let result = lending_pool.try_grant_role([7u8; 32], operator);
+    assert_eq!(result, Err(LendingError::InvalidRole.into()));
+}
+
+#[test]
+fn test_keeper_only_compound() {
+    let (env, mut thaw_core, _lending_pool, admin, operator) = setup();
+    let user = env.get_account(2);
+
+    // Open to anyone by default
+    assert!(!thaw_core.is_keeper_only_compound());
+    env.set_caller(user);
+    assert_eq!(thaw_core.compound(), U512::zero());
+
+    env.set_caller(admin);
+    thaw_core.set_keeper_only_compound(true);
+    thaw_core.grant_role(KEEPER_ROLE, operator);
+
+    env.set_caller(user);
+    assert_eq!(thaw_core.try_compound(), Err(Error::MissingRole.into()));
+    env.set_caller(operator);
+    assert_eq!(thaw_core.compound(), U512::zero());
+
+    env.set_caller(operator);
+    assert_eq!(
+        thaw_core.try_set_keeper_only_compound(false),
+        Err(Error::NotAdmin.into())
+    );
+}
+
+#[test]
+fn test_lending_pool_roles() {
+    let (env, _thaw_core, mut lending_pool, admin, operator) = setup();
+
+    env.set_caller(operator);
+    assert_eq!(
+        lending_pool.try_set_config(7000, 8000, 500),
+        Err(LendingError::MissingRole.into())
+    );
+
+    env.set_caller(admin);
+    lending_pool.grant_role(RISK_MANAGER_ROLE, operator);
+    assert!(lending_pool.has_role(RISK_MANAGER_ROLE, operator));
+
+    env.set_caller(operator);
+    lending_pool.set_config(7000, 8000, 500);
+    assert_eq!(
+        lending_pool.try_set_fee_recipients(Vec::new()),
+        Err(LendingError::MissingRole.into())
+    );
+}
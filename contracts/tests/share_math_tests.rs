@@ -0,0 +1,97 @@
+//! Share math property tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use proptest::prelude::*;
+
+use thaw::share_math::{to_assets, to_shares, Rounding, VIRTUAL_OFFSET};
+
+use test_utils::*;
+
+/// Largest donation an attacker is assumed to afford: 1M CSPR
+const MAX_DONATION: u64 = 1_000_000 * CSPR;
+
+#[test]
+fn test_empty_pool_is_one_to_one() {
+    let amount = U512::from(100u64 * CSPR);
+    assert_eq!(to_shares(amount, U512::zero(), U512::zero(), Rounding::Down), amount);
+}
+
+#[test]
+fn test_balanced_pool_is_one_to_one() {
+    let amount = U512::from(100u64 * CSPR);
+    let total = U512::from(5000u64 * CSPR);
+    assert_eq!(to_shares(amount, total, total, Rounding::Down), amount);
+    assert_eq!(to_assets(amount, total, total, Rounding::Down), amount);
+}
+
+#[test]
+fn test_rounding_direction() {
+    // 10 CSPR backing 3 shares: one share is worth 3.33... motes
+    let total_assets = U512::from(10u64 * CSPR);
+    let total_shares = U512::from(3u64 * CSPR);
+
+    let down = to_shares(U512::from(10u64), total_assets, total_shares, Rounding::Down);
+    let up = to_shares(U512::from(10u64), total_assets, total_shares, Rounding::Up);
+    assert_eq!(down, U512::from(3u64));
+    assert_eq!(up, U512::from(4u64));
+}
+
+#[test]
+fn test_donation_attack_does_not_zero_victim() {
+    // Attacker holds a single share and donates 1M CSPR to the pool
+    let total_shares = U512::one();
+    let total_assets = U512::one() + U512::from(MAX_DONATION);
+
+    let victim = to_shares(U512::from(MIN_STAKE), total_assets, total_shares, Rounding::Down);
+    assert!(victim > U512::zero());
+
+    // The attacker's single share captures almost none of the donation
+    let attacker_value = to_assets(U512::one(), total_assets, total_shares, Rounding::Down);
+    assert!(attacker_value < U512::from(MAX_DONATION / VIRTUAL_OFFSET * 2));
+}
+
+proptest! {
+    #[test]
+    fn prop_minimum_stake_never_rounds_to_zero(
+        attacker_shares in 1u64..1_000_000 * CSPR,
+        donation in 0u64..MAX_DONATION,
+        deposit in MIN_STAKE..100_000_000 * CSPR,
+    ) {
+        // Pool where an attacker front-ran the victim with a tiny stake and a donation
+        let total_shares = U512::from(attacker_shares);
+        let total_assets = U512::from(attacker_shares) + U512::from(donation);
+
+        let shares = to_shares(U512::from(deposit), total_assets, total_shares, Rounding::Down);
+        prop_assert!(shares > U512::zero());
+    }
+
+    #[test]
+    fn prop_stake_then_unstake_never_profits(
+        total_assets in 0u64..10_000_000_000 * CSPR,
+        total_shares in 0u64..10_000_000_000 * CSPR,
+        deposit in 1u64..100_000_000 * CSPR,
+    ) {
+        let (assets, shares) = (U512::from(total_assets), U512::from(total_shares));
+        let deposit = U512::from(deposit);
+
+        let minted = to_shares(deposit, assets, shares, Rounding::Down);
+        let redeemed = to_assets(minted, assets + deposit, shares + minted, Rounding::Down);
+        prop_assert!(redeemed <= deposit);
+    }
+
+    #[test]
+    fn prop_burn_rounds_up(
+        total_assets in 1u64..10_000_000_000 * CSPR,
+        total_shares in 1u64..10_000_000_000 * CSPR,
+        withdraw in 1u64..1_000_000 * CSPR,
+    ) {
+        let (assets, shares) = (U512::from(total_assets), U512::from(total_shares));
+        let withdraw = U512::from(withdraw);
+
+        // Shares burned for a CSPR amount are always worth at least that amount
+        let burned = to_shares(withdraw, assets, shares, Rounding::Up);
+        prop_assert!(to_assets(burned, assets, shares, Rounding::Down) >= withdraw);
+    }
+}
@@ -0,0 +1,306 @@
+//! Slashing and loss socialization tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::events::{Slashed, ValidatorEvicted};
+use thaw::share_math::{to_assets, Rounding};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup ThawCore with two equally weighted validators
+fn setup() -> (HostEnv, ThawCoreHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    thaw_core.add_validator(other_validators(&env, 1).remove(0), 10000);
+
+    (env, thaw_core, admin, user)
+}
+
+/// Exchange rate of a pool holding `pooled` CSPR against `supply` thCSPR
+fn rate_of(pooled: u64, supply: u64) -> U512 {
+    to_assets(
+        U512::from(EXCHANGE_RATE_PRECISION),
+        U512::from(pooled * CSPR),
+        U512::from(supply * CSPR),
+        Rounding::Down,
+    )
+}
+
+/// Evict the second validator and let its unbonding settle without the
+/// auction paying anything back, so the evicted delegation is missing
+fn evict_and_lose(env: &HostEnv, thaw_core: &mut ThawCoreHostRef) {
+    evict_validator(env, &other_validators(env, 1)[0]);
+    thaw_core.sync();
+    env.advance_block_time(UNBONDING_PERIOD_MS);
+}
+
+#[test]
+fn test_sync_without_loss() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    let rate_before = thaw_core.get_exchange_rate();
+
+    assert_eq!(thaw_core.sync(), U512::zero());
+    assert_eq!(thaw_core.get_exchange_rate(), rate_before);
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(2000u64 * CSPR));
+}
+
+#[test]
+fn test_eviction_tracked_as_unbonding() {
+    let (env, mut thaw_core, _admin, user) = setup();
+    let evicted = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+
+    evict_validator(&env, &evicted);
+
+    // The auction unbonds the delegation back to the pool
+    assert_eq!(thaw_core.sync(), U512::zero());
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(2000u64 * CSPR));
+    assert_eq!(thaw_core.get_pool_unbonding(), U512::from(1000u64 * CSPR));
+    assert_eq!(thaw_core.get_exchange_rate(), rate_of(2000, 2000));
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorEvicted {
+            validator: evicted,
+            amount: U512::from(1000u64 * CSPR),
+        }
+    ));
+
+    // Still within the unbonding delay
+    env.advance_block_time(UNBONDING_PERIOD_MS - 1);
+    assert_eq!(thaw_core.sync(), U512::zero());
+}
+
+#[test]
+fn test_sync_socializes_unreturned_unbonding() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+
+    evict_and_lose(&env, &mut thaw_core);
+
+    let loss = thaw_core.sync();
+    assert_eq!(loss, U512::from(1000u64 * CSPR), "Half of the pool is gone");
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(1000u64 * CSPR));
+
+    let half = rate_of(1000, 2000);
+    assert_eq!(thaw_core.get_exchange_rate(), half);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        Slashed {
+            loss,
+            holders_loss: loss,
+            withdrawals_loss: U512::zero(),
+            old_exchange_rate: U512::from(EXCHANGE_RATE_PRECISION),
+            new_exchange_rate: half,
+        }
+    ));
+}
+
+#[test]
+fn test_loss_shared_with_pending_withdrawals() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    let withdrawal_id = thaw_core.unstake(U512::from(400u64 * CSPR));
+
+    // 800 CSPR lost against 1600 pooled + 400 pending: withdrawals take 20%
+    evict_and_lose(&env, &mut thaw_core);
+    let loss = thaw_core.sync();
+
+    assert_eq!(loss, U512::from(800u64 * CSPR));
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(960u64 * CSPR));
+    assert_eq!(thaw_core.get_pool_unbonding(), U512::from(160u64 * CSPR));
+    assert_eq!(
+        thaw_core.get_withdrawal_payout(withdrawal_id),
+        Some(U512::from(240u64 * CSPR))
+    );
+
+    // Holders and the withdrawal lose the same 40%
+    assert_eq!(thaw_core.get_exchange_rate(), rate_of(960, 1600));
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        Slashed {
+            loss,
+            holders_loss: U512::from(640u64 * CSPR),
+            withdrawals_loss: U512::from(160u64 * CSPR),
+            old_exchange_rate: U512::from(EXCHANGE_RATE_PRECISION),
+            new_exchange_rate: rate_of(960, 1600),
+        }
+    ));
+}
+
+#[test]
+fn test_claim_pays_reduced_amount_after_loss() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    let withdrawal_id = thaw_core.unstake(U512::from(400u64 * CSPR));
+
+    evict_and_lose(&env, &mut thaw_core);
+    thaw_core.sync();
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+
+    let balance_before = env.balance_of(&user);
+    let claimed = thaw_core.claim(withdrawal_id);
+
+    assert_eq!(claimed, U512::from(240u64 * CSPR));
+    assert_eq!(env.balance_of(&user), balance_before + claimed);
+
+    // The request keeps its original amount for reference
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+    assert_eq!(withdrawal.cspr_amount, U512::from(400u64 * CSPR));
+}
+
+#[test]
+fn test_withdrawals_after_loss_are_not_haircut() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    thaw_core.unstake(U512::from(400u64 * CSPR));
+
+    evict_and_lose(&env, &mut thaw_core);
+    thaw_core.sync();
+
+    // New request at the lowered rate pays out exactly what it was quoted
+    let expected = to_assets(
+        U512::from(100u64 * CSPR),
+        thaw_core.get_total_pooled(),
+        thaw_core.get_total_supply(),
+        Rounding::Down,
+    );
+    let withdrawal_id = thaw_core.unstake(U512::from(100u64 * CSPR));
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+
+    assert_eq!(withdrawal.cspr_amount, expected);
+    assert!(exchange_rate_approx_eq(expected, U512::from(60u64 * CSPR), 1));
+    assert_eq!(
+        thaw_core.get_withdrawal_payout(withdrawal_id),
+        Some(withdrawal.cspr_amount)
+    );
+}
+
+#[test]
+fn test_eviction_does_not_haircut_withdrawals() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    let withdrawal_id = thaw_core.unstake(U512::from(400u64 * CSPR));
+
+    evict_validator(&env, &other_validators(&env, 1)[0]);
+    assert_eq!(thaw_core.sync(), U512::zero());
+    assert_eq!(
+        thaw_core.get_withdrawal_payout(withdrawal_id),
+        Some(U512::from(400u64 * CSPR))
+    );
+    assert_eq!(thaw_core.get_exchange_rate(), rate_of(1600, 1600));
+
+    // The evicted delegation and the withdrawal come back from unbonding
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    assert_eq!(thaw_core.claim(withdrawal_id), U512::from(400u64 * CSPR));
+
+    // The evicted 800 CSPR lands in the buffer without changing the rate
+    assert_eq!(thaw_core.sync(), U512::zero());
+    assert_eq!(thaw_core.get_pool_unbonding(), U512::zero());
+    assert_eq!(thaw_core.get_liquidity_buffer(), U512::from(800u64 * CSPR));
+    assert_eq!(thaw_core.get_total_pooled(), U512::from(1600u64 * CSPR));
+    assert_eq!(thaw_core.get_exchange_rate(), rate_of(1600, 1600));
+}
+
+#[test]
+fn test_sync_is_permissionless() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    evict_validator(&env, &other_validators(&env, 1)[0]);
+
+    env.set_caller(env.get_account(5));
+    assert!(thaw_core.try_sync().is_ok());
+    assert_eq!(thaw_core.get_pool_unbonding(), U512::from(1000u64 * CSPR));
+}
+
+#[test]
+fn test_unstake_is_priced_at_real_backing() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+
+    // Nobody syncs after the evicted delegation fails to come back
+    evict_and_lose(&env, &mut thaw_core);
+    let withdrawal_id = thaw_core.unstake(U512::from(400u64 * CSPR));
+
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+    assert!(exchange_rate_approx_eq(
+        withdrawal.cspr_amount,
+        U512::from(200u64 * CSPR),
+        1
+    ));
+    assert_eq!(
+        thaw_core.get_total_pooled() + withdrawal.cspr_amount,
+        U512::from(1000u64 * CSPR)
+    );
+}
+
+#[test]
+fn test_unstake_after_eviction_draws_on_unbonding() {
+    let (env, mut thaw_core, _admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+    evict_validator(&env, &other_validators(&env, 1)[0]);
+
+    // More than is still delegated: the evicted CSPR on its way back covers it
+    let withdrawal_id = thaw_core.unstake(U512::from(1500u64 * CSPR));
+    assert_eq!(
+        thaw_core.get_withdrawal_payout(withdrawal_id),
+        Some(U512::from(1500u64 * CSPR))
+    );
+    assert_eq!(thaw_core.get_pool_unbonding(), U512::from(500u64 * CSPR));
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    assert_eq!(thaw_core.claim(withdrawal_id), U512::from(1500u64 * CSPR));
+}
@@ -23,16 +23,12 @@ fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address, A
 
     let validator = create_mock_validator_key();
 
-    // Deploy mock auction contract
-    let mock_auction = deploy_mock_auction(&env);
-
     // Deploy ThawCore first with admin as placeholder token
     let mut thaw_core = ThawCore::deploy(
         &env,
         ThawCoreInitArgs {
             thcspr_token: admin, // placeholder, will update after token deploy
             validator: validator.clone(),
-            auction_address: mock_auction.address(),
             treasury,
             admin,
         },
@@ -56,7 +52,7 @@ fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address, A
 #[test]
 fn test_stake_minimum() {
     // Stake exactly 10 CSPR → receive 10 thCSPR (1:1 initial)
-    let (env, mut thaw_core, thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -80,7 +76,7 @@ fn test_stake_minimum() {
 #[test]
 fn test_stake_below_minimum() {
     // Stake 5 CSPR → revert "Below minimum"
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -123,7 +119,7 @@ fn test_stake_when_paused() {
 #[test]
 fn test_stake_updates_totals() {
     // Stake 100 CSPR → total_pooled = 100, total_supply = 100
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -146,7 +142,7 @@ fn test_stake_updates_totals() {
 #[test]
 fn test_stake_multiple_users() {
     // Multiple users stake, totals accumulate correctly
-    let (env, mut thaw_core, thcspr_token, _admin, _treasury, _user) = setup();
+    let (env, thaw_core, thcspr_token, _admin, _treasury, _user) = setup();
 
     let user1 = env.get_account(2);
     let user2 = env.get_account(3);
@@ -176,7 +172,7 @@ fn test_stake_multiple_users() {
 #[test]
 fn test_stake_emits_event() {
     // Verify Staked event is emitted
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
     let stake_amount = U512::from(100u64 * CSPR);
@@ -189,6 +185,7 @@ fn test_stake_emits_event() {
         cspr_amount: stake_amount,
         thcspr_minted: stake_amount, // 1:1 on first stake
         exchange_rate: U512::from(EXCHANGE_RATE_PRECISION), // 1e18 = 1:1 rate
+        referrer: None,
     };
 
     assert!(
@@ -200,7 +197,7 @@ fn test_stake_emits_event() {
 #[test]
 fn test_first_deposit_exchange_rate() {
     // First deposit always 1:1
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     // Initial exchange rate should be 1:1 (1e18)
     let initial_rate = thaw_core.get_exchange_rate();
@@ -221,7 +218,7 @@ fn test_first_deposit_exchange_rate() {
 #[test]
 fn test_stake_large_amount() {
     // Test staking a large amount (1 million CSPR)
-    let (env, mut thaw_core, thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -239,7 +236,7 @@ fn test_stake_large_amount() {
 #[test]
 fn test_stake_exactly_minimum() {
     // Stake exactly at minimum boundary
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -253,7 +250,7 @@ fn test_stake_exactly_minimum() {
 #[test]
 fn test_stake_just_below_minimum() {
     // Stake 1 mote below minimum
-    let (env, mut thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -268,7 +265,7 @@ fn test_stake_just_below_minimum() {
 #[test]
 fn test_consecutive_stakes_same_user() {
     // Same user stakes multiple times
-    let (env, mut thaw_core, thcspr_token, _admin, _treasury, user) = setup();
+    let (env, thaw_core, thcspr_token, _admin, _treasury, user) = setup();
 
     env.set_caller(user);
 
@@ -293,3 +290,89 @@ fn test_consecutive_stakes_same_user() {
     assert_eq!(thaw_core.get_total_pooled(), expected_total);
     assert_eq!(thaw_core.get_total_supply(), expected_total);
 }
+
+#[test]
+fn test_stake_for_mints_to_recipient() {
+    // A partner stakes on behalf of a user who receives the thCSPR
+    let (env, thaw_core, thcspr_token, _admin, _treasury, user) = setup();
+    let partner = env.get_account(3);
+
+    env.set_caller(partner);
+    let stake_amount = U512::from(100u64 * CSPR);
+    thaw_core.with_tokens(stake_amount).stake_for(user, None);
+
+    assert_eq!(thcspr_token.balance_of(user), stake_amount.as_u128().into());
+    assert_eq!(thcspr_token.balance_of(partner), 0u128.into());
+    assert_eq!(thaw_core.get_total_pooled(), stake_amount);
+}
+
+#[test]
+fn test_stake_for_tracks_referral_totals() {
+    // Stakes with a referrer are summed per referrer
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let user2 = env.get_account(3);
+    let referrer = env.get_account(4);
+
+    env.set_caller(user);
+    let stake1 = U512::from(100u64 * CSPR);
+    thaw_core.with_tokens(stake1).stake_for(user, Some(referrer));
+
+    env.set_caller(user2);
+    let stake2 = U512::from(40u64 * CSPR);
+    thaw_core.with_tokens(stake2).stake_for(user2, Some(referrer));
+
+    // Plain stakes and stakes without a referrer are not counted
+    thaw_core.with_tokens(stake2).stake();
+    thaw_core.with_tokens(stake2).stake_for(user2, None);
+
+    assert_eq!(thaw_core.get_referral_total(referrer), stake1 + stake2);
+    assert_eq!(thaw_core.get_referral_total(user), U512::zero());
+}
+
+#[test]
+fn test_stake_for_emits_referrer() {
+    // Staked event carries the recipient and referrer
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+    let partner = env.get_account(3);
+    let referrer = env.get_account(4);
+
+    env.set_caller(partner);
+    let stake_amount = U512::from(100u64 * CSPR);
+    thaw_core.with_tokens(stake_amount).stake_for(user, Some(referrer));
+
+    let expected_event = Staked {
+        user,
+        cspr_amount: stake_amount,
+        thcspr_minted: stake_amount,
+        exchange_rate: U512::from(EXCHANGE_RATE_PRECISION),
+        referrer: Some(referrer),
+    };
+    assert!(env.emitted_event(&thaw_core, expected_event));
+}
+
+#[test]
+fn test_stake_for_self_referral_fails() {
+    // Recipient cannot refer their own stake
+    let (env, thaw_core, _thcspr_token, _admin, _treasury, user) = setup();
+
+    env.set_caller(user);
+    let result = thaw_core
+        .with_tokens(U512::from(100u64 * CSPR))
+        .try_stake_for(user, Some(user));
+    assert_eq!(result, Err(Error::InvalidReferrer.into()));
+}
+
+#[test]
+fn test_stake_for_when_paused() {
+    // stake_for respects the pause like stake
+    let (env, mut thaw_core, _thcspr_token, admin, _treasury, user) = setup();
+
+    env.set_caller(admin);
+    thaw_core.pause();
+
+    env.set_caller(user);
+    let result = thaw_core
+        .with_tokens(U512::from(100u64 * CSPR))
+        .try_stake_for(user, None);
+    assert_eq!(result, Err(Error::ContractPaused.into()));
+}
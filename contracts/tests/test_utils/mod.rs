@@ -1,12 +1,13 @@
 //! Test utilities and helpers for Thaw protocol tests
 
-pub mod mock_auction;
-
-pub use mock_auction::{MockAuction, MockAuctionHostRef};
+#![allow(dead_code)]
 
 use odra::casper_types::{PublicKey, SecretKey, U512};
-use odra::host::{Deployer, HostEnv, NoArgs};
-use odra::prelude::*;
+use odra::host::HostEnv;
+
+use thaw::lending_pool::LendingPoolHostRef;
+use thaw::thaw_core::ThawCoreHostRef;
+use thaw::timelock::DEFAULT_TIMELOCK_DELAY_MS;
 
 /// Constants for testing
 pub const CSPR: u64 = 1_000_000_000; // 1 CSPR in motes (9 decimals)
@@ -21,6 +22,43 @@ pub fn create_mock_validator_key() -> PublicKey {
     PublicKey::from(&secret_key)
 }
 
+/// Get `count` auction validators that differ from the mock validator key
+pub fn other_validators(env: &HostEnv, count: usize) -> Vec<PublicKey> {
+    let primary = create_mock_validator_key();
+    (0..5)
+        .map(|i| env.get_validator(i))
+        .filter(|v| *v != primary)
+        .take(count)
+        .collect()
+}
+
+/// Evict an auction validator, unbonding every delegation to it
+pub fn evict_validator(env: &HostEnv, validator: &PublicKey) {
+    let index = (0..5)
+        .find(|i| env.get_validator(*i) == *validator)
+        .expect("not an auction validator");
+    env.remove_validator(index);
+}
+
+/// Turn the ThawCore timelock off so parameter changes apply immediately
+///
+/// Lowering the delay waits out the default delay, so this advances the block
+/// time by it. Call it as the admin, before anything time-sensitive happens.
+pub fn disable_timelock(env: &HostEnv, thaw_core: &mut ThawCoreHostRef) {
+    thaw_core.set_timelock_delay(0);
+    let id = thaw_core.get_queued_operations().last().unwrap().id;
+    env.advance_block_time(DEFAULT_TIMELOCK_DELAY_MS);
+    thaw_core.execute_operation(id);
+}
+
+/// Turn the LendingPool timelock off, see `disable_timelock`
+pub fn disable_lending_timelock(env: &HostEnv, lending_pool: &mut LendingPoolHostRef) {
+    lending_pool.set_timelock_delay(0);
+    let id = lending_pool.get_queued_operations().last().unwrap().id;
+    env.advance_block_time(DEFAULT_TIMELOCK_DELAY_MS);
+    lending_pool.execute_operation(id);
+}
+
 /// Helper to convert U512 to u128 for easier assertions
 pub fn to_u128(value: U512) -> u128 {
     value.as_u128()
@@ -36,8 +74,3 @@ pub fn exchange_rate_approx_eq(actual: U512, expected: U512, tolerance_pct: u64)
     };
     diff <= tolerance
 }
-
-/// Deploy a mock auction contract for testing
-pub fn deploy_mock_auction(env: &HostEnv) -> MockAuctionHostRef {
-    MockAuction::deploy(env, NoArgs)
-}
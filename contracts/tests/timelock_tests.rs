@@ -0,0 +1,384 @@
+//! Timelock tests for Thaw protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::{Error, LendingError};
+use thaw::events::{FeeUpdated, OperationCancelled, OperationExecuted, OperationQueued};
+use thaw::fee_splitter::FeeRecipient;
+use thaw::lending_pool::{LendingPool, LendingPoolHostRef, LendingPoolInitArgs};
+use thaw::roles::FEE_MANAGER_ROLE;
+use thaw::thaw_core::{FeeMode, ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+use thaw::timelock::{TimelockedCall, DEFAULT_TIMELOCK_DELAY_MS, MAX_TIMELOCK_DELAY_MS};
+
+use test_utils::*;
+
+const DELAY_MS: u64 = 48 * 60 * 60 * 1000; // 48 hours
+
+/// Helper to setup linked contracts with a 48h timelock on both
+fn setup() -> (HostEnv, ThawCoreHostRef, LendingPoolHostRef, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+
+    let lending_pool = LendingPool::deploy(
+        &env,
+        LendingPoolInitArgs {
+            thaw_core: thaw_core.address(),
+            thcspr_token: thcspr_token.address(),
+            admin,
+        },
+    );
+
+    (env, thaw_core, lending_pool, admin)
+}
+
+#[test]
+fn test_default_delay() {
+    let (env, thaw_core, lending_pool, _admin) = setup();
+
+    assert_eq!(DEFAULT_TIMELOCK_DELAY_MS, DELAY_MS);
+    assert_eq!(thaw_core.get_timelock_delay(), DELAY_MS);
+    assert_eq!(lending_pool.get_timelock_delay(), DELAY_MS);
+
+    // Wiring the token before anything is minted skips the queue
+    assert!(thaw_core.get_queued_operations().is_empty());
+    assert!(lending_pool.get_queued_operations().is_empty());
+    env.set_caller(env.get_account(2));
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+    assert_eq!(thaw_core.get_total_supply(), U512::from(100u64 * CSPR));
+}
+
+#[test]
+fn test_fee_change_waits_for_delay() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+    let old_fee = thaw_core.get_protocol_fee_bps();
+
+    env.set_caller(admin);
+    thaw_core.set_protocol_fee(500);
+    assert_eq!(thaw_core.get_protocol_fee_bps(), old_fee);
+
+    let eta = env.block_time() + DELAY_MS;
+    let queued = thaw_core.get_queued_operations();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].call, TimelockedCall::SetProtocolFee { fee_bps: 500 });
+    assert_eq!(queued[0].eta, eta);
+    assert_eq!(thaw_core.get_queued_operation(queued[0].id), Some(queued[0].clone()));
+    assert!(env.emitted_event(
+        &thaw_core,
+        OperationQueued {
+            id: queued[0].id,
+            eta,
+        }
+    ));
+
+    // Too early
+    env.advance_block_time(DELAY_MS - 1);
+    let result = thaw_core.try_execute_operation(queued[0].id);
+    assert_eq!(result, Err(Error::TimelockNotReady.into()));
+
+    // Anyone can execute once the ETA has passed
+    env.advance_block_time(1);
+    env.set_caller(env.get_account(2));
+    thaw_core.execute_operation(queued[0].id);
+    assert_eq!(thaw_core.get_protocol_fee_bps(), 500);
+    assert!(thaw_core.get_queued_operations().is_empty());
+    assert!(env.emitted_event(&thaw_core, OperationExecuted { id: queued[0].id }));
+    assert!(env.emitted_event(
+        &thaw_core,
+        FeeUpdated {
+            old_fee_bps: old_fee,
+            new_fee_bps: 500,
+        }
+    ));
+
+    // Executed operations cannot run twice
+    let result = thaw_core.try_execute_operation(queued[0].id);
+    assert_eq!(result, Err(Error::OperationNotQueued.into()));
+}
+
+#[test]
+fn test_sensitive_setters_are_queued() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+    let new_treasury = env.get_account(4);
+    let new_token = env.get_account(5);
+    let others = other_validators(&env, 2);
+    let new_validator = others[0].clone();
+
+    // Once thCSPR exists, swapping the token waits like everything else
+    env.set_caller(env.get_account(2));
+    thaw_core.with_tokens(U512::from(100u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.set_treasury(new_treasury);
+    thaw_core.set_thcspr_token(new_token);
+    thaw_core.set_validator(new_validator.clone());
+    thaw_core.start_validator_migration(create_mock_validator_key(), others[1].clone());
+
+    let calls: Vec<TimelockedCall> = thaw_core
+        .get_queued_operations()
+        .into_iter()
+        .map(|op| op.call)
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            TimelockedCall::SetTreasury {
+                treasury: new_treasury
+            },
+            TimelockedCall::SetThcsprToken {
+                thcspr_token: new_token
+            },
+            TimelockedCall::SetValidator {
+                validator: new_validator.clone()
+            },
+            TimelockedCall::StartValidatorMigration {
+                from: create_mock_validator_key(),
+                to: others[1].clone(),
+            },
+        ]
+    );
+    assert_ne!(thaw_core.get_treasury(), Some(new_treasury));
+    assert_ne!(thaw_core.get_validator(), Some(new_validator));
+    assert_eq!(thaw_core.get_migration(), None);
+}
+
+#[test]
+fn test_validator_set_changes_wait_for_delay() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+    let primary = create_mock_validator_key();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    thaw_core.add_validator(new_validator.clone(), 10000);
+    thaw_core.set_validator_weight(primary.clone(), 0);
+    thaw_core.remove_validator(primary.clone());
+
+    let queued = thaw_core.get_queued_operations();
+    assert_eq!(
+        queued.iter().map(|op| op.call.clone()).collect::<Vec<_>>(),
+        vec![
+            TimelockedCall::AddValidator {
+                validator: new_validator.clone(),
+                weight: 10000,
+            },
+            TimelockedCall::SetValidatorWeight {
+                validator: primary.clone(),
+                weight: 0,
+            },
+            TimelockedCall::RemoveValidator {
+                validator: primary.clone(),
+            },
+        ]
+    );
+
+    // Nothing changes before the delay
+    env.advance_block_time(DELAY_MS - 1);
+    assert_eq!(thaw_core.get_validators(), vec![primary.clone()]);
+    assert_eq!(thaw_core.get_total_validator_weight(), 10000);
+    for op in &queued {
+        let result = thaw_core.try_execute_operation(op.id);
+        assert_eq!(result, Err(Error::TimelockNotReady.into()));
+    }
+
+    env.advance_block_time(1);
+    for op in &queued {
+        thaw_core.execute_operation(op.id);
+    }
+    assert_eq!(thaw_core.get_validators(), vec![new_validator]);
+    assert_eq!(thaw_core.get_total_validator_weight(), 10000);
+    assert!(thaw_core.get_validator_allocation(primary).is_none());
+}
+
+#[test]
+fn test_fee_and_risk_setters_are_queued() {
+    let (env, mut thaw_core, mut lending_pool, admin) = setup();
+    let recipients = vec![FeeRecipient {
+        account: env.get_account(4),
+        share_bps: 10000,
+    }];
+
+    env.set_caller(admin);
+    thaw_core.set_fee_mode(FeeMode::Shares);
+    thaw_core.set_fee_recipients(recipients.clone());
+    thaw_core.set_keeper_reward_config(100, U512::from(CSPR), 0);
+    thaw_core.set_min_stake(U512::from(CSPR));
+    thaw_core.set_stake_caps(U512::from(1000u64 * CSPR), U512::zero());
+    thaw_core.set_cap_exempt(lending_pool.address(), true);
+    thaw_core.set_delegation_threshold(U512::from(500u64 * CSPR));
+    thaw_core.set_unbonding_delay(DELAY_MS);
+    thaw_core.set_liquidity_buffer_config(1000, 1000, 10, 300);
+    assert_eq!(thaw_core.get_queued_operations().len(), 9);
+    assert_eq!(thaw_core.get_fee_mode(), FeeMode::Cspr);
+    assert!(thaw_core.get_fee_recipients().is_empty());
+    assert_eq!(thaw_core.get_stake_cap(), U512::zero());
+    assert!(!thaw_core.is_cap_exempt(lending_pool.address()));
+
+    lending_pool.set_fee_recipients(recipients.clone());
+    lending_pool.set_staking_apr(900);
+    let queued = lending_pool.get_queued_operations();
+    assert_eq!(
+        queued.iter().map(|op| op.call.clone()).collect::<Vec<_>>(),
+        vec![
+            TimelockedCall::SetFeeRecipients {
+                recipients: recipients.clone()
+            },
+            TimelockedCall::SetStakingApr { apr_bps: 900 },
+        ]
+    );
+    assert!(lending_pool.get_fee_recipients().is_empty());
+
+    env.advance_block_time(DELAY_MS);
+    for operation in thaw_core.get_queued_operations() {
+        thaw_core.execute_operation(operation.id);
+    }
+    for operation in queued {
+        lending_pool.execute_operation(operation.id);
+    }
+    assert_eq!(thaw_core.get_fee_mode(), FeeMode::Shares);
+    assert_eq!(thaw_core.get_fee_recipients(), recipients);
+    assert_eq!(thaw_core.get_stake_cap(), U512::from(1000u64 * CSPR));
+    assert!(thaw_core.is_cap_exempt(lending_pool.address()));
+    assert_eq!(lending_pool.get_fee_recipients(), recipients);
+    assert_eq!(lending_pool.get_staking_apr(), 900);
+}
+
+#[test]
+fn test_cancel_operation() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+    let fee_manager = env.get_account(3);
+
+    env.set_caller(admin);
+    thaw_core.grant_role(FEE_MANAGER_ROLE, fee_manager);
+    thaw_core.set_protocol_fee(500);
+    let id = thaw_core.get_queued_operations()[0].id;
+
+    env.set_caller(env.get_account(2));
+    assert_eq!(thaw_core.try_cancel_operation(id), Err(Error::MissingRole.into()));
+
+    env.set_caller(fee_manager);
+    thaw_core.cancel_operation(id);
+    assert!(env.emitted_event(&thaw_core, OperationCancelled { id }));
+    assert_eq!(thaw_core.get_queued_operation(id), None);
+
+    env.advance_block_time(DELAY_MS);
+    assert_eq!(
+        thaw_core.try_execute_operation(id),
+        Err(Error::OperationNotQueued.into())
+    );
+    assert_eq!(thaw_core.try_cancel_operation(id), Err(Error::OperationNotQueued.into()));
+}
+
+#[test]
+fn test_delay_change_is_timelocked() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_set_timelock_delay(MAX_TIMELOCK_DELAY_MS + 1);
+    assert_eq!(result, Err(Error::InvalidTimelockDelay.into()));
+
+    thaw_core.set_timelock_delay(0);
+    assert_eq!(thaw_core.get_timelock_delay(), DELAY_MS);
+    let id = thaw_core.get_queued_operations()[0].id;
+
+    env.advance_block_time(DELAY_MS);
+    thaw_core.execute_operation(id);
+    assert_eq!(thaw_core.get_timelock_delay(), 0);
+
+    // Back to instant changes
+    thaw_core.set_protocol_fee(700);
+    assert_eq!(thaw_core.get_protocol_fee_bps(), 700);
+}
+
+#[test]
+fn test_pause_stays_instant() {
+    let (env, mut thaw_core, _lending_pool, admin) = setup();
+
+    env.set_caller(admin);
+    thaw_core.pause();
+    assert!(thaw_core.is_paused());
+    assert!(thaw_core.get_queued_operations().is_empty());
+}
+
+#[test]
+fn test_lending_pool_config_is_timelocked() {
+    let (env, _thaw_core, mut lending_pool, admin) = setup();
+
+    env.set_caller(admin);
+    lending_pool.set_config(7000, 8500, 600);
+    let queued = lending_pool.get_queued_operations();
+    assert_eq!(
+        queued[0].call,
+        TimelockedCall::SetLendingConfig {
+            collateral_factor: 7000,
+            liq_threshold: 8500,
+            liq_bonus: 600,
+        }
+    );
+
+    let result = lending_pool.try_execute_operation(queued[0].id);
+    assert_eq!(result, Err(LendingError::TimelockNotReady.into()));
+
+    env.advance_block_time(DELAY_MS);
+    lending_pool.execute_operation(queued[0].id);
+    assert!(lending_pool.get_queued_operations().is_empty());
+
+    lending_pool.set_config(7500, 8000, 500);
+    let id = lending_pool.get_queued_operations()[0].id;
+    env.set_caller(env.get_account(2));
+    assert_eq!(
+        lending_pool.try_cancel_operation(id),
+        Err(LendingError::MissingRole.into())
+    );
+    env.set_caller(admin);
+    lending_pool.cancel_operation(id);
+    assert!(lending_pool.get_queued_operations().is_empty());
+}
+
+#[test]
+fn test_reserve_withdrawal_is_timelocked() {
+    let (env, _thaw_core, mut lending_pool, admin) = setup();
+    let to = env.get_account(4);
+
+    env.set_caller(admin);
+    lending_pool.withdraw_reserves(to, U512::from(CSPR));
+    let queued = lending_pool.get_queued_operations();
+    assert_eq!(
+        queued[0].call,
+        TimelockedCall::WithdrawReserves {
+            to,
+            amount: U512::from(CSPR),
+        }
+    );
+
+    // Reserves are checked when the withdrawal is applied
+    env.advance_block_time(DELAY_MS);
+    let result = lending_pool.try_execute_operation(queued[0].id);
+    assert_eq!(result, Err(LendingError::InsufficientReserves.into()));
+    assert_eq!(lending_pool.get_queued_operations().len(), 1);
+}
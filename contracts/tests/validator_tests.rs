@@ -0,0 +1,402 @@
+//! Validator set tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::Error;
+use thaw::events::{
+    ValidatorAdded, ValidatorMigrationCompleted, ValidatorMigrationStarted, ValidatorRedelegated,
+    ValidatorRemoved, ValidatorWeightUpdated,
+};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup test environment
+fn setup() -> (HostEnv, ThawCoreHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let validator = create_mock_validator_key();
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator,
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+
+    (env, thaw_core, admin, user)
+}
+
+#[test]
+fn test_initial_validator_set() {
+    let (_env, thaw_core, _admin, _user) = setup();
+
+    let primary = create_mock_validator_key();
+    assert_eq!(thaw_core.get_validators(), vec![primary.clone()]);
+
+    let allocation = thaw_core.get_validator_allocation(primary).unwrap();
+    assert_eq!(allocation.target_bps, 10000, "Single validator targets 100%");
+    assert_eq!(allocation.delegated, U512::zero());
+}
+
+#[test]
+fn test_add_validator() {
+    let (env, mut thaw_core, admin, _user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    thaw_core.add_validator(new_validator.clone(), 10000);
+
+    assert_eq!(thaw_core.get_validators().len(), 2);
+    assert_eq!(thaw_core.get_total_validator_weight(), 20000);
+
+    let allocation = thaw_core.get_validator_allocation(new_validator.clone()).unwrap();
+    assert_eq!(allocation.weight, 10000);
+    assert_eq!(allocation.target_bps, 5000);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorAdded {
+            validator: new_validator,
+            weight: 10000,
+        }
+    ));
+}
+
+#[test]
+fn test_add_duplicate_validator() {
+    let (env, mut thaw_core, admin, _user) = setup();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_add_validator(create_mock_validator_key(), 5000);
+    assert_eq!(result.unwrap_err(), Error::ValidatorAlreadyExists.into());
+}
+
+#[test]
+fn test_non_admin_add_validator() {
+    let (env, mut thaw_core, _admin, user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    let result = thaw_core.try_add_validator(new_validator, 5000);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
+
+#[test]
+fn test_stake_splits_by_weight() {
+    // 3:1 weights → 75% / 25% of the stake
+    let (env, mut thaw_core, admin, user) = setup();
+    let primary = create_mock_validator_key();
+    let second = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    thaw_core.set_validator_weight(primary.clone(), 7500);
+    thaw_core.add_validator(second.clone(), 2500);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    let first_alloc = thaw_core.get_validator_allocation(primary).unwrap();
+    let second_alloc = thaw_core.get_validator_allocation(second).unwrap();
+    assert_eq!(first_alloc.delegated, U512::from(750u64 * CSPR));
+    assert_eq!(second_alloc.delegated, U512::from(250u64 * CSPR));
+    assert_eq!(first_alloc.target_bps, 7500);
+    assert_eq!(second_alloc.target_bps, 2500);
+}
+
+#[test]
+fn test_stake_fills_underweight_validator_first() {
+    // New validator joins after stake → next stake goes to it
+    let (env, mut thaw_core, admin, user) = setup();
+    let primary = create_mock_validator_key();
+    let second = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.add_validator(second.clone(), 10000);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(600u64 * CSPR)).stake();
+
+    let first_alloc = thaw_core.get_validator_allocation(primary).unwrap();
+    let second_alloc = thaw_core.get_validator_allocation(second).unwrap();
+    assert_eq!(first_alloc.delegated, U512::from(1000u64 * CSPR));
+    assert_eq!(second_alloc.delegated, U512::from(600u64 * CSPR));
+}
+
+#[test]
+fn test_unstake_drains_zero_weight_validator_first() {
+    let (env, mut thaw_core, admin, user) = setup();
+    let primary = create_mock_validator_key();
+    let second = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    thaw_core.add_validator(second.clone(), 10000);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(2000u64 * CSPR)).stake();
+
+    // Phase out the primary validator
+    env.set_caller(admin);
+    thaw_core.set_validator_weight(primary.clone(), 0);
+
+    env.set_caller(user);
+    thaw_core.unstake(U512::from(1000u64 * CSPR));
+
+    let first_alloc = thaw_core.get_validator_allocation(primary.clone()).unwrap();
+    let second_alloc = thaw_core.get_validator_allocation(second).unwrap();
+    assert_eq!(first_alloc.delegated, U512::zero());
+    assert_eq!(second_alloc.delegated, U512::from(1000u64 * CSPR));
+
+    // Empty validator can now be removed
+    env.set_caller(admin);
+    thaw_core.remove_validator(primary.clone());
+    assert!(thaw_core.get_validator_allocation(primary.clone()).is_none());
+    assert!(env.emitted_event(&thaw_core, ValidatorRemoved { validator: primary }));
+}
+
+#[test]
+fn test_remove_validator_with_delegation() {
+    let (env, mut thaw_core, admin, user) = setup();
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_remove_validator(create_mock_validator_key());
+    assert_eq!(result.unwrap_err(), Error::ValidatorHasDelegation.into());
+}
+
+#[test]
+fn test_remove_unknown_validator() {
+    let (env, mut thaw_core, admin, _user) = setup();
+    let unknown = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    let result = thaw_core.try_remove_validator(unknown);
+    assert_eq!(result.unwrap_err(), Error::ValidatorNotFound.into());
+}
+
+#[test]
+fn test_set_validator_weight_emits_event() {
+    let (env, mut thaw_core, admin, _user) = setup();
+    let primary = create_mock_validator_key();
+
+    env.set_caller(admin);
+    thaw_core.set_validator_weight(primary.clone(), 4000);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorWeightUpdated {
+            validator: primary,
+            old_weight: 10000,
+            new_weight: 4000,
+        }
+    ));
+}
+
+#[test]
+fn test_stake_without_active_validators() {
+    let (env, mut thaw_core, admin, user) = setup();
+
+    env.set_caller(admin);
+    thaw_core.set_validator_weight(create_mock_validator_key(), 0);
+
+    env.set_caller(user);
+    let result = thaw_core.with_tokens(U512::from(100u64 * CSPR)).try_stake();
+    assert_eq!(result.unwrap_err(), Error::NoActiveValidators.into());
+}
+
+#[test]
+fn test_validator_migration_flow() {
+    // Stake → migrate → wait for unbonding → re-delegate to new validator
+    let (env, mut thaw_core, admin, user) = setup();
+    let primary = create_mock_validator_key();
+    let new_validator = other_validators(&env, 1).remove(0);
+    let stake_amount = U512::from(1000u64 * CSPR);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(stake_amount).stake();
+    let rate_before = thaw_core.get_exchange_rate();
+
+    env.set_caller(admin);
+    thaw_core.start_validator_migration(primary.clone(), new_validator.clone());
+
+    // Old validator leaves the set, CSPR is in flight
+    assert_eq!(thaw_core.get_validators(), vec![new_validator.clone()]);
+    assert_eq!(thaw_core.get_pending_redelegation(), stake_amount);
+    assert_eq!(thaw_core.get_migration().unwrap().amount, stake_amount);
+    assert_eq!(thaw_core.get_exchange_rate(), rate_before, "Rate must not move");
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorMigrationStarted {
+            from: primary.clone(),
+            to: new_validator.clone(),
+            amount: stake_amount,
+        }
+    ));
+
+    // Nothing is back before unbonding completes
+    let result = thaw_core.try_complete_validator_migration();
+    assert_eq!(result.unwrap_err(), Error::NothingToRedelegate.into());
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS);
+
+    // Anyone can finish the migration
+    env.set_caller(user);
+    let redelegated = thaw_core.complete_validator_migration();
+    assert_eq!(redelegated, stake_amount);
+    assert_eq!(thaw_core.get_pending_redelegation(), U512::zero());
+    assert!(thaw_core.get_migration().is_none());
+    assert_eq!(thaw_core.get_exchange_rate(), rate_before, "Rate must not move");
+
+    let allocation = thaw_core.get_validator_allocation(new_validator.clone()).unwrap();
+    assert_eq!(allocation.delegated, stake_amount);
+    assert_eq!(allocation.weight, 10000);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorRedelegated {
+            from: primary.clone(),
+            to: new_validator.clone(),
+            amount: stake_amount,
+            remaining: U512::zero(),
+        }
+    ));
+    assert!(env.emitted_event(
+        &thaw_core,
+        ValidatorMigrationCompleted {
+            from: primary,
+            to: new_validator,
+            amount: stake_amount,
+        }
+    ));
+}
+
+#[test]
+fn test_set_validator_migrates_primary() {
+    let (env, mut thaw_core, admin, user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.set_validator(new_validator.clone());
+
+    assert_eq!(thaw_core.get_validator(), Some(new_validator.clone()));
+    let migration = thaw_core.get_migration().unwrap();
+    assert_eq!(migration.from, create_mock_validator_key());
+    assert_eq!(migration.to, new_validator);
+}
+
+#[test]
+fn test_migration_without_delegation_completes_immediately() {
+    let (env, mut thaw_core, admin, _user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(admin);
+    thaw_core.set_validator(new_validator.clone());
+
+    assert_eq!(thaw_core.get_validators(), vec![new_validator]);
+    assert!(thaw_core.get_migration().is_none());
+    assert_eq!(thaw_core.get_pending_redelegation(), U512::zero());
+}
+
+#[test]
+fn test_unstake_during_migration_uses_pending_redelegation() {
+    let (env, mut thaw_core, admin, user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.start_validator_migration(create_mock_validator_key(), new_validator.clone());
+
+    // Nothing is delegated, the withdrawal is paid from the in-flight CSPR
+    env.set_caller(user);
+    let withdrawal_id = thaw_core.unstake(U512::from(400u64 * CSPR));
+    assert_eq!(thaw_core.get_pending_redelegation(), U512::from(600u64 * CSPR));
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS);
+
+    // Only the non-reserved part is re-delegated
+    let redelegated = thaw_core.complete_validator_migration();
+    assert_eq!(redelegated, U512::from(600u64 * CSPR));
+
+    let claimed = thaw_core.claim(withdrawal_id);
+    assert_eq!(claimed, U512::from(400u64 * CSPR));
+    assert_eq!(
+        thaw_core.get_validator_allocation(new_validator).unwrap().delegated,
+        U512::from(600u64 * CSPR)
+    );
+}
+
+#[test]
+fn test_second_migration_while_in_progress() {
+    let (env, mut thaw_core, admin, user) = setup();
+    let others = other_validators(&env, 2);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+
+    env.set_caller(admin);
+    thaw_core.start_validator_migration(create_mock_validator_key(), others[0].clone());
+    let result = thaw_core.try_start_validator_migration(others[0].clone(), others[1].clone());
+    assert_eq!(result.unwrap_err(), Error::MigrationInProgress.into());
+}
+
+#[test]
+fn test_migration_to_same_validator() {
+    let (env, mut thaw_core, admin, _user) = setup();
+    let primary = create_mock_validator_key();
+
+    env.set_caller(admin);
+    let result = thaw_core.try_start_validator_migration(primary.clone(), primary);
+    assert_eq!(result.unwrap_err(), Error::SameValidator.into());
+}
+
+#[test]
+fn test_complete_without_migration() {
+    let (_env, mut thaw_core, _admin, _user) = setup();
+
+    let result = thaw_core.try_complete_validator_migration();
+    assert_eq!(result.unwrap_err(), Error::NoMigrationInProgress.into());
+}
+
+#[test]
+fn test_non_admin_start_migration() {
+    let (env, mut thaw_core, _admin, user) = setup();
+    let new_validator = other_validators(&env, 1).remove(0);
+
+    env.set_caller(user);
+    let result =
+        thaw_core.try_start_validator_migration(create_mock_validator_key(), new_validator);
+    assert_eq!(result.unwrap_err(), Error::MissingRole.into());
+}
@@ -0,0 +1,155 @@
+//! Vault view tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenHostRef, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup a pool whose exchange rate is above 1:1
+///
+/// An instant unstake leaves its fee in the pool, so the rate ends up
+/// with a remainder that exercises rounding.
+fn setup() -> (HostEnv, ThawCoreHostRef, ThCsprTokenHostRef, Address, Address) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator: create_mock_validator_key(),
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+    disable_timelock(&env, &mut thaw_core);
+    thaw_core.set_liquidity_buffer_config(2000, 2000, 100, 300);
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    thaw_core.instant_unstake(U512::from(77u64 * CSPR), 300);
+
+    (env, thaw_core, thcspr_token, admin, user)
+}
+
+#[test]
+fn test_preview_stake_matches_stake() {
+    let (env, thaw_core, _thcspr_token, _admin, _user) = setup();
+    assert!(thaw_core.get_exchange_rate() > U512::from(EXCHANGE_RATE_PRECISION));
+
+    let amount = U512::from(333u64 * CSPR + 7);
+    let preview = thaw_core.preview_stake(amount);
+
+    env.set_caller(env.get_account(3));
+    let minted = thaw_core.with_tokens(amount).stake();
+
+    assert_eq!(preview, minted);
+    assert!(preview < amount, "Rate above 1:1 mints fewer shares");
+}
+
+#[test]
+fn test_preview_unstake_matches_unstake() {
+    let (_env, mut thaw_core, _thcspr_token, _admin, _user) = setup();
+
+    let amount = U512::from(123u64 * CSPR + 11);
+    let preview = thaw_core.preview_unstake(amount);
+
+    let withdrawal_id = thaw_core.unstake(amount);
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+
+    assert_eq!(preview, withdrawal.cspr_amount);
+    assert!(preview > amount, "Rate above 1:1 releases more CSPR");
+}
+
+#[test]
+fn test_preview_withdraw_rounds_up() {
+    let (_env, mut thaw_core, _thcspr_token, _admin, _user) = setup();
+
+    let cspr_amount = U512::from(123u64 * CSPR + 11);
+    let thcspr_amount = thaw_core.preview_withdraw(cspr_amount);
+
+    // Burning one share less would fall short, the previewed amount never does
+    assert!(thaw_core.preview_unstake(thcspr_amount - 1) < cspr_amount);
+    let withdrawal_id = thaw_core.unstake(thcspr_amount);
+    let withdrawal = thaw_core.get_withdrawal(withdrawal_id).unwrap();
+    assert!(withdrawal.cspr_amount >= cspr_amount);
+}
+
+#[test]
+fn test_convert_matches_previews() {
+    let (_env, thaw_core, _thcspr_token, _admin, _user) = setup();
+
+    let amount = U512::from(50u64 * CSPR + 3);
+    assert_eq!(thaw_core.convert_to_shares(amount), thaw_core.preview_stake(amount));
+    assert_eq!(thaw_core.convert_to_assets(amount), thaw_core.preview_unstake(amount));
+
+    // Round trip never gains
+    let shares = thaw_core.convert_to_shares(amount);
+    assert!(thaw_core.convert_to_assets(shares) <= amount);
+}
+
+#[test]
+fn test_stake_unstake_round_trip_never_gains() {
+    let (env, mut thaw_core, thcspr_token, _admin, _user) = setup();
+
+    let amounts = [
+        10 * CSPR + 1,
+        10 * CSPR + 999_999,
+        333 * CSPR + 7,
+        1000 * CSPR + 13,
+    ];
+    for (i, amount) in amounts.into_iter().enumerate() {
+        let staker = env.get_account(3 + i);
+        env.set_caller(staker);
+
+        let staked = U512::from(amount);
+        thaw_core.with_tokens(staked).stake();
+        let balance = U512::from(thcspr_token.balance_of(staker).as_u128());
+        let withdrawal_id = thaw_core.unstake(balance);
+
+        env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+        let claimed = thaw_core.claim(withdrawal_id);
+        assert!(claimed <= staked, "Round trip of {amount} motes returned {claimed}");
+    }
+}
+
+#[test]
+fn test_max_stake_and_unstake() {
+    let (env, mut thaw_core, thcspr_token, admin, user) = setup();
+
+    assert_eq!(thaw_core.max_stake(user), U512::from(u64::MAX));
+    let balance = U512::from(thcspr_token.balance_of(user).as_u128());
+    assert_eq!(thaw_core.max_unstake(user), balance);
+    assert_eq!(thaw_core.max_unstake(env.get_account(5)), U512::zero());
+
+    // Room under the per-address cap below the 10 CSPR minimum
+    env.set_caller(admin);
+    let position = thaw_core.convert_to_assets(balance);
+    thaw_core.set_stake_caps(U512::zero(), position + U512::from(5u64 * CSPR));
+    assert_eq!(thaw_core.max_stake(user), U512::zero());
+    thaw_core.set_stake_caps(U512::zero(), position + U512::from(50u64 * CSPR));
+    assert_eq!(thaw_core.max_stake(user), U512::from(50u64 * CSPR));
+
+    thaw_core.pause();
+    assert_eq!(thaw_core.max_stake(user), U512::zero());
+    assert_eq!(thaw_core.max_unstake(user), U512::zero());
+}
@@ -0,0 +1,168 @@
+//! Withdrawal ticket transfer tests for Thaw liquid staking protocol
+
+mod test_utils;
+
+use odra::casper_types::U512;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::*;
+
+use thaw::errors::Error;
+use thaw::events::{WithdrawalApproved, WithdrawalTransferred};
+use thaw::thaw_core::{ThawCore, ThawCoreHostRef, ThawCoreInitArgs};
+use thaw::thcspr_token::{ThCsprToken, ThCsprTokenInitArgs};
+
+use test_utils::*;
+
+/// Helper to setup test environment with user owning a withdrawal
+fn setup_with_withdrawal() -> (HostEnv, ThawCoreHostRef, Address, Address, u64) {
+    let env = odra_test::env();
+
+    let admin = env.get_account(0);
+    let treasury = env.get_account(1);
+    let user = env.get_account(2);
+    let buyer = env.get_account(3);
+
+    let validator = create_mock_validator_key();
+
+    let mut thaw_core = ThawCore::deploy(
+        &env,
+        ThawCoreInitArgs {
+            thcspr_token: admin, // placeholder, will update after token deploy
+            validator,
+            treasury,
+            admin,
+        },
+    );
+
+    let thcspr_token = ThCsprToken::deploy(
+        &env,
+        ThCsprTokenInitArgs {
+            minter: thaw_core.address(),
+        },
+    );
+
+    env.set_caller(admin);
+    thaw_core.set_thcspr_token(thcspr_token.address());
+
+    env.set_caller(user);
+    thaw_core.with_tokens(U512::from(1000u64 * CSPR)).stake();
+    let withdrawal_id = thaw_core.unstake(U512::from(100u64 * CSPR));
+
+    (env, thaw_core, user, buyer, withdrawal_id)
+}
+
+#[test]
+fn test_transfer_withdrawal_by_owner() {
+    let (env, mut thaw_core, user, buyer, withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(user);
+    thaw_core.transfer_withdrawal(withdrawal_id, buyer);
+
+    assert_eq!(thaw_core.get_withdrawal(withdrawal_id).unwrap().user, buyer);
+    assert!(thaw_core.get_user_withdrawals(user).is_empty());
+    assert_eq!(thaw_core.get_user_withdrawals(buyer).len(), 1);
+
+    assert!(env.emitted_event(
+        &thaw_core,
+        WithdrawalTransferred {
+            withdrawal_id,
+            from: user,
+            to: buyer,
+        }
+    ));
+}
+
+#[test]
+fn test_new_owner_claims() {
+    let (env, mut thaw_core, user, buyer, withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(user);
+    thaw_core.transfer_withdrawal(withdrawal_id, buyer);
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+
+    // Previous owner can no longer claim
+    let result = thaw_core.try_claim(withdrawal_id);
+    assert_eq!(result.unwrap_err(), Error::NotWithdrawalOwner.into());
+
+    env.set_caller(buyer);
+    let claimed = thaw_core.claim(withdrawal_id);
+    assert_eq!(claimed, U512::from(100u64 * CSPR));
+}
+
+#[test]
+fn test_transfer_by_approved_spender() {
+    let (env, mut thaw_core, user, buyer, withdrawal_id) = setup_with_withdrawal();
+    let desk = env.get_account(4);
+
+    env.set_caller(user);
+    thaw_core.approve_withdrawal(withdrawal_id, Some(desk));
+    assert_eq!(thaw_core.get_withdrawal_approval(withdrawal_id), Some(desk));
+    assert!(env.emitted_event(
+        &thaw_core,
+        WithdrawalApproved {
+            withdrawal_id,
+            owner: user,
+            spender: Some(desk),
+        }
+    ));
+
+    env.set_caller(desk);
+    thaw_core.transfer_withdrawal(withdrawal_id, buyer);
+
+    assert_eq!(thaw_core.get_withdrawal(withdrawal_id).unwrap().user, buyer);
+    assert_eq!(thaw_core.get_withdrawal_approval(withdrawal_id), None, "Approval cleared");
+}
+
+#[test]
+fn test_transfer_not_approved() {
+    let (env, mut thaw_core, _user, buyer, withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(buyer);
+    let result = thaw_core.try_transfer_withdrawal(withdrawal_id, buyer);
+    assert_eq!(result.unwrap_err(), Error::NotWithdrawalApproved.into());
+}
+
+#[test]
+fn test_revoked_approval() {
+    let (env, mut thaw_core, user, buyer, withdrawal_id) = setup_with_withdrawal();
+    let desk = env.get_account(4);
+
+    env.set_caller(user);
+    thaw_core.approve_withdrawal(withdrawal_id, Some(desk));
+    thaw_core.approve_withdrawal(withdrawal_id, None);
+
+    env.set_caller(desk);
+    let result = thaw_core.try_transfer_withdrawal(withdrawal_id, buyer);
+    assert_eq!(result.unwrap_err(), Error::NotWithdrawalApproved.into());
+}
+
+#[test]
+fn test_approve_not_owner() {
+    let (env, mut thaw_core, _user, buyer, withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(buyer);
+    let result = thaw_core.try_approve_withdrawal(withdrawal_id, Some(buyer));
+    assert_eq!(result.unwrap_err(), Error::NotWithdrawalOwner.into());
+}
+
+#[test]
+fn test_transfer_claimed_withdrawal() {
+    let (env, mut thaw_core, user, buyer, withdrawal_id) = setup_with_withdrawal();
+
+    env.advance_with_auctions(UNBONDING_PERIOD_MS + 1);
+    env.set_caller(user);
+    thaw_core.claim(withdrawal_id);
+
+    let result = thaw_core.try_transfer_withdrawal(withdrawal_id, buyer);
+    assert_eq!(result.unwrap_err(), Error::AlreadyClaimed.into());
+}
+
+#[test]
+fn test_transfer_unknown_withdrawal() {
+    let (env, mut thaw_core, user, buyer, _withdrawal_id) = setup_with_withdrawal();
+
+    env.set_caller(user);
+    let result = thaw_core.try_transfer_withdrawal(999, buyer);
+    assert_eq!(result.unwrap_err(), Error::WithdrawalNotFound.into());
+}
@@ -37,6 +37,7 @@ export const CASPER_CHAIN_NAME = NETWORK_CONFIG[CURRENT_NETWORK].chainName;
 export const THAW_CORE_HASH: string = "hash-6dcfc9d903e1c8757503b44f0bd104fcbbe2cd9807dcdf0fe4e9044382596b79";
 export const THCSPR_TOKEN_HASH: string = "hash-075f46fd3f4a5f382e8083dfd8ac9bbe9af012c0bc7acef2d84186be337f789e";
 export const LENDING_POOL_HASH: string = ""; // Optional: Not yet deployed
+export const TCSPR_TOKEN_HASH: string = ""; // Optional: Deployed with the lending pool
 
 // Contract package hashes (if using versioned contracts)
 export const THAW_CORE_PACKAGE_HASH: string = "";
@@ -96,17 +97,22 @@ export function motesToCspr(motes: bigint | string): number {
 // Exchange rate precision (1e18)
 export const EXCHANGE_RATE_PRECISION = BigInt("1000000000000000000");
 
+// Virtual shares and assets added to every share conversion (share_math.rs)
+export const SHARE_VIRTUAL_OFFSET = BigInt(1_000_000);
+
+// thCSPR minted for a stake, rounded down like preview_stake
 export function calculateThcsprFromCspr(
   csprAmount: bigint,
   totalPooled: bigint,
   totalSupply: bigint
 ): bigint {
-  if (totalSupply === BigInt(0) || totalPooled === BigInt(0)) {
-    return csprAmount;
-  }
-  return (csprAmount * totalSupply) / totalPooled;
+  return (
+    (csprAmount * (totalSupply + SHARE_VIRTUAL_OFFSET)) /
+    (totalPooled + SHARE_VIRTUAL_OFFSET)
+  );
 }
 
+// CSPR queued for an unstake, rounded down like preview_unstake
 export function calculateCsprFromThcspr(
   thcsprAmount: bigint,
   totalPooled: bigint,
@@ -115,7 +121,10 @@ export function calculateCsprFromThcspr(
   if (totalSupply === BigInt(0)) {
     return BigInt(0);
   }
-  return (thcsprAmount * totalPooled) / totalSupply;
+  const cspr =
+    (thcsprAmount * (totalPooled + SHARE_VIRTUAL_OFFSET)) /
+    (totalSupply + SHARE_VIRTUAL_OFFSET);
+  return cspr < totalPooled ? cspr : totalPooled;
 }
 
 // ============================================
@@ -575,7 +584,9 @@ export async function getPoolStats(): Promise<PoolStats> {
 
     let exchangeRate = EXCHANGE_RATE_PRECISION;
     if (totalSupplyBigInt > 0) {
-      exchangeRate = (totalPooledBigInt * EXCHANGE_RATE_PRECISION) / totalSupplyBigInt;
+      exchangeRate =
+        (EXCHANGE_RATE_PRECISION * (totalPooledBigInt + SHARE_VIRTUAL_OFFSET)) /
+        (totalSupplyBigInt + SHARE_VIRTUAL_OFFSET);
     }
 
     return {
@@ -650,14 +661,38 @@ export async function getUserPosition(accountHash: string): Promise<UserPosition
   }
 
   try {
-    const [collateral, borrowed, lenderDeposit] = await Promise.all([
+    const [
+      collateral,
+      scaledDebt,
+      borrowIndex,
+      lenderShares,
+      totalDeposits,
+      totalLenderShares,
+    ] = await Promise.all([
       queryDictionary(LENDING_POOL_HASH, "collateral_balances", accountHash),
-      queryDictionary(LENDING_POOL_HASH, "borrowed_balances", accountHash),
-      queryDictionary(LENDING_POOL_HASH, "lender_deposits", accountHash),
+      queryDictionary(LENDING_POOL_HASH, "scaled_debt", accountHash),
+      queryContractState(LENDING_POOL_HASH, "borrow_index"),
+      getTokenBalance(accountHash, TCSPR_TOKEN_HASH),
+      queryContractState(LENDING_POOL_HASH, "total_deposits"),
+      queryContractState(LENDING_POOL_HASH, "total_lender_shares"),
     ]);
 
     const collateralBigInt = BigInt(String(collateral || "0"));
-    const borrowedBigInt = BigInt(String(borrowed || "0"));
+
+    // Debt is stored scaled by the borrow index; grow it back to CSPR the way
+    // get_position does (rounding up). Interest since the last accrual is
+    // not in the stored index yet.
+    const scaledDebtBigInt = BigInt(String(scaledDebt || "0"));
+    const borrowIndexBigInt = BigInt(String(borrowIndex || EXCHANGE_RATE_PRECISION));
+    const borrowedBigInt =
+      (scaledDebtBigInt * borrowIndexBigInt + EXCHANGE_RATE_PRECISION - BigInt(1)) /
+      EXCHANGE_RATE_PRECISION;
+
+    // The deposit is held as tCSPR shares; value them the way
+    // get_lender_deposit does, virtual offset included
+    const lenderDeposit =
+      (lenderShares * (BigInt(String(totalDeposits || "0")) + SHARE_VIRTUAL_OFFSET)) /
+      (BigInt(String(totalLenderShares || "0")) + SHARE_VIRTUAL_OFFSET);
 
     // Calculate health factor
     let healthFactor = EXCHANGE_RATE_PRECISION * BigInt(10); // Very healthy if no debt
@@ -674,7 +709,7 @@ export async function getUserPosition(accountHash: string): Promise<UserPosition
       borrowed: borrowedBigInt,
       healthFactor,
       maxBorrow: maxBorrow > 0 ? maxBorrow : BigInt(0),
-      lenderDeposit: BigInt(String(lenderDeposit || "0")),
+      lenderDeposit,
     };
   } catch (error) {
     console.error("Failed to get user position:", error);